@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use crate::{
+    diagnostics::{
+        registry::{INCONSISTENT_ARGUMENTS, LOCAL_OUT_OF_RANGE, MISSING_ARGUMENT},
+        Diagnostic,
+    },
+    parser::{Command, CommandType}, program::Program,
+};
+
+
+pub fn check_arity(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = check_call_arguments(program);
+    diagnostics.extend(check_local_indices(program));
+    diagnostics
+}
+
+fn check_call_arguments(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut max_arguments: HashMap<&str, (i32, usize)> = HashMap::new();
+    for file in &program.files {
+        for function in file.functions() {
+            let max_argument = function.commands.iter()
+                .filter(|command| is_segment_access(command, "argument"))
+                .max_by_key(|command| command.get_arg2());
+            if let Some(command) = max_argument {
+                max_arguments.insert(
+                    function.name, (command.get_arg2().unwrap_or(0), command.get_line_number()));
+            }
+        }
+    }
+
+    let mut first_calls: HashMap<&str, (i32, &str, usize)> = HashMap::new();
+    for file in &program.files {
+        for command in &file.commands {
+            if command.get_command_type() != &CommandType::Call {
+                continue;
+            }
+            let callee = command.get_arg1();
+            let nargs = command.get_arg2().unwrap_or(0);
+
+            if let Some(&(max_argument, line_number)) = max_arguments.get(callee) {
+                if max_argument >= nargs {
+                    diagnostics.push(Diagnostic::new(
+                        &MISSING_ARGUMENT,
+                        &file.path,
+                        command.get_line_number(),
+                        format!("call to '{}' passes {} argument(s) but '{}' uses argument {} at line {}",
+                            callee, nargs, callee, max_argument, line_number),
+                    ));
+                }
+            }
+
+            match first_calls.get(callee) {
+                Some(&(first_nargs, path, line_number)) if first_nargs != nargs => {
+                    diagnostics.push(Diagnostic::new(
+                        &INCONSISTENT_ARGUMENTS,
+                        &file.path,
+                        command.get_line_number(),
+                        format!("call to '{}' passes {} argument(s) but the call at {}:{} passes {}",
+                            callee, nargs, path, line_number, first_nargs),
+                    ));
+                },
+                Some(_) => {},
+                None => {
+                    first_calls.insert(callee, (nargs, &file.path, command.get_line_number()));
+                },
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn check_local_indices(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for file in &program.files {
+        for function in file.functions() {
+            let nlocals = function.commands[0].get_arg2().unwrap_or(0);
+            for command in function.commands {
+                let index = command.get_arg2().unwrap_or(0);
+                if is_segment_access(command, "local") && index >= nlocals {
+                    diagnostics.push(Diagnostic::new(
+                        &LOCAL_OUT_OF_RANGE,
+                        &file.path,
+                        command.get_line_number(),
+                        format!("local {} is out of range for '{}', which declares {} local(s)",
+                            index, function.name, nlocals),
+                    ));
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn is_segment_access(command: &Command, segment: &str) -> bool {
+    matches!(command.get_command_type(), CommandType::Push | CommandType::Pop)
+        && command.get_arg1() == segment
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::{
+            registry::{INCONSISTENT_ARGUMENTS, LOCAL_OUT_OF_RANGE, MISSING_ARGUMENT},
+            Diagnostic,
+        },
+        program::Program,
+    };
+
+    use super::check_arity;
+
+    #[test]
+    fn test_check_arity_given_consistent_program() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 1\npush constant 1\npush constant 2\ncall Math.add 2\npop local 0\nreturn"),
+            ("Math", "function Math.add 0\npush argument 0\npush argument 1\nadd\nreturn"),
+        ]);
+
+        assert!(check_arity(&program).is_empty());
+    }
+
+    #[test]
+    fn test_check_arity_given_missing_argument() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\npush constant 1\ncall Math.add 1\nreturn"),
+            ("Math", "function Math.add 0\npush argument 0\npush argument 1\nadd\nreturn"),
+        ]);
+
+        assert_eq!(check_arity(&program), vec![
+            Diagnostic::new(&MISSING_ARGUMENT, "Main.vm", 3, String::from(
+                "call to 'Math.add' passes 1 argument(s) but 'Math.add' uses argument 1 at line 3")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_arity_given_inconsistent_calls() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Output.printInt 1\ncall Output.printInt 2\nreturn"),
+        ]);
+
+        assert_eq!(check_arity(&program), vec![
+            Diagnostic::new(&INCONSISTENT_ARGUMENTS, "Main.vm", 3, String::from(
+                "call to 'Output.printInt' passes 2 argument(s) but the call at Main.vm:2 passes 1")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_arity_given_local_out_of_range() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 2\npush local 1\npop local 2\nreturn"),
+        ]);
+
+        assert_eq!(check_arity(&program), vec![
+            Diagnostic::new(&LOCAL_OUT_OF_RANGE, "Main.vm", 3, String::from(
+                "local 2 is out of range for 'Main.main', which declares 2 local(s)")),
+        ]);
+    }
+}
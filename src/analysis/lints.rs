@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    diagnostics::{
+        registry::{
+            UNINITIALIZED_STATIC, UNREACHABLE_CODE, UNUSED_FUNCTION, UNUSED_LABEL, UNUSED_STATIC,
+        },
+        Diagnostic,
+    },
+    parser::{Command, CommandType}, program::Program,
+};
+
+
+pub fn check_lints(program: &Program, is_whole_program: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for file in &program.files {
+        diagnostics.extend(check_scope(&file.path, &file.name, file.top_level_commands()));
+        for function in file.functions() {
+            diagnostics.extend(check_scope(&file.path, function.name, &function.commands[1..]));
+        }
+        diagnostics.extend(check_statics(&file.path, &file.commands));
+    }
+
+    if is_whole_program {
+        diagnostics.extend(check_unused_functions(program));
+    }
+
+    diagnostics
+}
+
+fn check_scope(path: &str, scope: &str, commands: &[Command]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut jump: Option<&str> = None;
+    let mut is_reported = false;
+
+    for command in commands {
+        if command.get_command_type() == &CommandType::Label {
+            is_reported = false;
+        } else if let Some(jump_name) = jump {
+            if !is_reported {
+                diagnostics.push(Diagnostic::new(
+                    &UNREACHABLE_CODE,
+                    path,
+                    command.get_line_number(),
+                    format!("unreachable code after '{}' in '{}'", jump_name, scope),
+                ));
+                is_reported = true;
+            }
+            continue;
+        }
+        jump = match command.get_command_type() {
+            CommandType::Goto => Some("goto"),
+            CommandType::Return => Some("return"),
+            _ => None,
+        };
+    }
+
+    let targets: HashSet<&str> = commands.iter()
+        .filter(|command| matches!(command.get_command_type(), CommandType::Goto | CommandType::If))
+        .map(Command::get_arg1)
+        .collect();
+    for command in commands {
+        if command.get_command_type() == &CommandType::Label && !targets.contains(command.get_arg1()) {
+            diagnostics.push(Diagnostic::new(
+                &UNUSED_LABEL,
+                path,
+                command.get_line_number(),
+                format!("label '{}' is never used in '{}'", command.get_arg1(), scope),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+fn check_statics(path: &str, commands: &[Command]) -> Vec<Diagnostic> {
+    let mut accesses: BTreeMap<i32, (Option<&Command>, Option<&Command>)> = BTreeMap::new();
+    for command in commands {
+        if command.get_arg1() != "static" {
+            continue;
+        }
+        let access = accesses.entry(command.get_arg2().unwrap_or(0)).or_default();
+        match command.get_command_type() {
+            CommandType::Push => { access.0.get_or_insert(command); },
+            CommandType::Pop => { access.1.get_or_insert(command); },
+            _ => {},
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    for (index, access) in accesses {
+        match access {
+            (None, Some(write)) => diagnostics.push(Diagnostic::new(
+                &UNUSED_STATIC,
+                path,
+                write.get_line_number(),
+                format!("static {} is written but never read", index),
+            )),
+            (Some(read), None) => diagnostics.push(Diagnostic::new(
+                &UNINITIALIZED_STATIC,
+                path,
+                read.get_line_number(),
+                format!("static {} is read but never written", index),
+            )),
+            _ => {},
+        }
+    }
+    diagnostics
+}
+
+fn check_unused_functions(program: &Program) -> Vec<Diagnostic> {
+    let callees: HashSet<&str> = program.files.iter()
+        .flat_map(|file| file.commands.iter())
+        .filter(|command| command.get_command_type() == &CommandType::Call)
+        .map(Command::get_arg1)
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    for file in &program.files {
+        for function in file.functions() {
+            if function.name != "Sys.init" && !callees.contains(function.name) {
+                diagnostics.push(Diagnostic::new(
+                    &UNUSED_FUNCTION,
+                    &file.path,
+                    function.commands[0].get_line_number(),
+                    format!("function '{}' is never called", function.name),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::Program;
+
+    use super::*;
+
+    fn check(text: &str) -> Vec<Diagnostic> {
+        check_lints(&Program::new(&[("Main", text)]), true)
+    }
+
+    #[test]
+    fn test_check_lints_given_clean_program() {
+        assert!(check("function Sys.init 0
+            label LOOP
+            push static 0
+            if-goto END
+            push constant 1
+            pop static 0
+            goto LOOP
+            label END
+            push constant 0
+            return").is_empty());
+    }
+
+    #[test]
+    fn test_check_lints_given_unreachable_code() {
+        assert_eq!(check("function Sys.init 0\nlabel LOOP\ngoto LOOP\npush constant 1\nadd\nlabel END\nreturn"), vec![
+            Diagnostic::new(&UNREACHABLE_CODE, "Main.vm", 4, String::from("unreachable code after 'goto' in 'Sys.init'")),
+            Diagnostic::new(&UNUSED_LABEL, "Main.vm", 6, String::from("label 'END' is never used in 'Sys.init'")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_lints_given_statics() {
+        assert_eq!(check("function Sys.init 0\npop static 1\npush static 2\npush static 2\nreturn"), vec![
+            Diagnostic::new(&UNUSED_STATIC, "Main.vm", 2, String::from("static 1 is written but never read")),
+            Diagnostic::new(&UNINITIALIZED_STATIC, "Main.vm", 3, String::from("static 2 is read but never written")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_lints_given_unused_function() {
+        assert_eq!(check("function Sys.init 0\ncall Main.used 0\nreturn
+            function Main.used 0\nreturn
+            function Main.unused 0\nreturn"), vec![
+            Diagnostic::new(&UNUSED_FUNCTION, "Main.vm", 6, String::from("function 'Main.unused' is never called")),
+        ]);
+    }
+}
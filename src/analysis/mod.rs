@@ -0,0 +1,21 @@
+use crate::{diagnostics::Diagnostic, program::Program};
+
+pub mod arity;
+pub mod lints;
+pub mod stack_balance;
+pub mod stack_usage;
+pub mod symbols;
+
+
+pub fn analyze(program: &Program, is_whole_program: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = symbols::check_symbols(program, is_whole_program);
+    diagnostics.extend(stack_balance::check_stack_balance(program));
+    diagnostics.extend(arity::check_arity(program));
+    diagnostics.extend(lints::check_lints(program, is_whole_program));
+    if is_whole_program {
+        diagnostics.extend(stack_usage::check_stack_usage(program, "Sys.init"));
+    }
+
+    diagnostics.sort_by(|a, b| (&a.path, a.line_number).cmp(&(&b.path, b.line_number)));
+    diagnostics
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use crate::{
+    cfg::ControlFlowGraph,
+    diagnostics::{
+        registry::{Lint, STACK_MISMATCH, STACK_UNDERFLOW, UNBALANCED_RETURN},
+        Diagnostic,
+    },
+    parser::{Command, CommandType}, program::Program,
+};
+
+
+pub struct StackAnalysis {
+    pub depths: Vec<Option<i32>>,
+    pub errors: Vec<(usize, &'static Lint, String)>,
+}
+
+pub fn check_stack_balance(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for file in &program.files {
+        for function in file.functions() {
+            let body = &function.commands[1..];
+            for (index, lint, message) in analyze_stack(body).errors {
+                diagnostics.push(Diagnostic::new(
+                    lint,
+                    &file.path,
+                    body[index].get_line_number(),
+                    format!("in '{}': {}", function.name, message),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+pub fn analyze_stack(body: &[Command]) -> StackAnalysis {
+    let successors = get_successors(body);
+    let mut depths: Vec<Option<i32>> = vec![None; body.len()];
+    let mut errors = Vec::new();
+    let mut mismatches = HashSet::new();
+    let mut pending = Vec::new();
+
+    if !body.is_empty() {
+        depths[0] = Some(0);
+        pending.push(0);
+    }
+
+    while let Some(index) = pending.pop() {
+        let command = &body[index];
+        let depth = depths[index].unwrap();
+        let (pops, pushes) = get_stack_effect(command);
+
+        if depth < pops {
+            errors.push((index, &STACK_UNDERFLOW, format!(
+                "stack underflow: '{}' needs {} value(s) but the stack holds {}",
+                command.get_name(), pops, depth)));
+            continue;
+        }
+        if command.get_command_type() == &CommandType::Return && depth != 1 {
+            errors.push((index, &UNBALANCED_RETURN, format!("stack depth at return is {}, expected 1", depth)));
+        }
+
+        let next_depth = depth - pops + pushes;
+        for &successor in &successors[index] {
+            match depths[successor] {
+                None => {
+                    depths[successor] = Some(next_depth);
+                    pending.push(successor);
+                },
+                Some(known_depth) if known_depth != next_depth => {
+                    if mismatches.insert(successor) {
+                        errors.push((successor, &STACK_MISMATCH, format!(
+                            "stack depth mismatch at label '{}': {} on one path, {} on another",
+                            body[successor].get_arg1(), known_depth, next_depth)));
+                    }
+                },
+                Some(_) => {},
+            }
+        }
+    }
+
+    errors.sort_by_key(|(index, _, _)| *index);
+    StackAnalysis { depths, errors }
+}
+
+fn get_successors(body: &[Command]) -> Vec<Vec<usize>> {
+    let cfg = ControlFlowGraph::new(body);
+    let mut successors = Vec::new();
+    for block in cfg.blocks() {
+        successors.extend((block.start + 1..block.end).map(|next| vec![next]));
+        successors.push(block.successors.iter().map(|&successor| cfg.blocks()[successor].start).collect());
+    }
+    successors
+}
+
+pub fn get_stack_effect(command: &Command) -> (i32, i32) {
+    match (command.get_command_type(), command.get_arg1()) {
+        (CommandType::Push, _) => (0, 1),
+        (CommandType::Pop, _) => (1, 0),
+        (CommandType::Arithmetic, "neg" | "not") => (1, 1),
+        (CommandType::Arithmetic, _) => (2, 1),
+        (CommandType::If, _) => (1, 0),
+        (CommandType::Call, _) => (command.get_arg2().unwrap_or(0), 1),
+        (CommandType::Return, _) => (1, 0),
+        (CommandType::Label | CommandType::Goto | CommandType::Function, _) => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::{
+            registry::{STACK_MISMATCH, STACK_UNDERFLOW, UNBALANCED_RETURN},
+            Diagnostic,
+        },
+        program::{Program, VmFile},
+    };
+
+    use super::{analyze_stack, check_stack_balance};
+
+    fn check(text: &str) -> Vec<Diagnostic> {
+        check_stack_balance(&Program::new(&[("Main", text)]))
+    }
+
+    #[test]
+    fn test_analyze_stack() {
+        let file = VmFile::new("Main", "function Main.main 0
+            push constant 1
+            if-goto ELSE
+            push constant 2
+            goto END
+            label ELSE
+            push constant 3
+            label END
+            return");
+        let analysis = analyze_stack(&file.commands[1..]);
+
+        assert!(analysis.errors.is_empty());
+        assert_eq!(analysis.depths, vec![
+            Some(0), Some(1), Some(0), Some(1), Some(0), Some(0), Some(1), Some(1),
+        ]);
+    }
+
+    #[test]
+    fn test_check_stack_balance_given_balanced_loop() {
+        assert!(check("function Main.main 1
+            label LOOP
+            push local 0
+            if-goto END
+            push constant 1
+            pop local 0
+            goto LOOP
+            label END
+            push constant 0
+            return").is_empty());
+    }
+
+    #[test]
+    fn test_check_stack_balance_given_mismatch_at_join() {
+        assert_eq!(check("function Main.main 0
+            push constant 1
+            if-goto ELSE
+            push constant 2
+            push constant 2
+            goto END
+            label ELSE
+            push constant 3
+            label END
+            return"), vec![
+            Diagnostic::new(&STACK_MISMATCH, "Main.vm", 9, String::from(
+                "in 'Main.main': stack depth mismatch at label 'END': 1 on one path, 2 on another")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_stack_balance_given_underflow() {
+        assert_eq!(check("function Main.main 0\npush constant 1\nadd\nreturn"), vec![
+            Diagnostic::new(&STACK_UNDERFLOW, "Main.vm", 3, String::from(
+                "in 'Main.main': stack underflow: 'add' needs 2 value(s) but the stack holds 1")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_stack_balance_given_extra_value_at_return() {
+        assert_eq!(check("function Main.main 0\npush constant 1\npush constant 2\nreturn"), vec![
+            Diagnostic::new(&UNBALANCED_RETURN, "Main.vm", 4, String::from(
+                "in 'Main.main': stack depth at return is 2, expected 1")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_stack_balance_given_call() {
+        assert!(check("function Main.main 0\npush constant 1\npush constant 2\ncall Math.add 2\nreturn")
+            .is_empty());
+    }
+}
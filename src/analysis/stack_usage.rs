@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use crate::{
+    analysis::stack_balance::{analyze_stack, get_stack_effect},
+    call_graph::CallGraph,
+    diagnostics::{registry::{STACK_OVERFLOW, UNBOUNDED_RECURSION}, Diagnostic},
+    parser::CommandType, program::Program,
+};
+
+pub const STACK_START: i32 = 256;
+pub const STACK_END: i32 = 2047;
+const FRAME_SIZE: i32 = 5;
+
+
+struct FunctionUsage {
+    path: String,
+    line_number: usize,
+    nlocals: i32,
+    max_depth: i32,
+    calls: Vec<(String, i32)>,
+}
+
+pub fn check_stack_usage(program: &Program, entry_function: &str) -> Vec<Diagnostic> {
+    let call_graph = CallGraph::new(program);
+    if !call_graph.is_defined(entry_function) {
+        return Vec::new();
+    }
+
+    let usages = get_function_usages(program);
+    let reachable = call_graph.reachable_from(&[entry_function.to_string()]);
+    let mut diagnostics = Vec::new();
+
+    for cycle in call_graph.recursive_cycles() {
+        if !reachable.contains(&cycle[0]) {
+            continue;
+        }
+        let usage = &usages[&cycle[0]];
+        let members: Vec<String> = cycle.iter().map(|name| format!("'{}'", name)).collect();
+        diagnostics.push(Diagnostic::new(
+            &UNBOUNDED_RECURSION,
+            &usage.path,
+            usage.line_number,
+            format!("recursion through {} makes the worst-case stack usage unbounded",
+                members.join(", ")),
+        ));
+    }
+
+    let stack_size = STACK_END - STACK_START + 1;
+    let worst_case = get_worst_case_usage(entry_function, &usages, &mut HashMap::new(), &mut Vec::new());
+    if worst_case > stack_size {
+        let usage = &usages[entry_function];
+        diagnostics.push(Diagnostic::new(
+            &STACK_OVERFLOW,
+            &usage.path,
+            usage.line_number,
+            format!("worst-case stack usage from '{}' is {} words, exceeding the {} words between RAM {} and {}",
+                entry_function, worst_case, stack_size, STACK_START, STACK_END),
+        ));
+    }
+
+    diagnostics
+}
+
+fn get_function_usages(program: &Program) -> HashMap<String, FunctionUsage> {
+    let mut usages = HashMap::new();
+
+    for file in &program.files {
+        for function in file.functions() {
+            let body = &function.commands[1..];
+            let analysis = analyze_stack(body);
+            let mut max_depth = 0;
+            let mut calls = Vec::new();
+
+            for (command, depth) in body.iter().zip(analysis.depths) {
+                let Some(depth) = depth else {
+                    continue;
+                };
+                let (pops, pushes) = get_stack_effect(command);
+                max_depth = max_depth.max(depth).max(depth - pops + pushes);
+                if command.get_command_type() == &CommandType::Call {
+                    calls.push((command.get_arg1().to_string(), depth));
+                }
+            }
+
+            usages.insert(function.name.to_string(), FunctionUsage {
+                path: file.path.clone(),
+                line_number: function.commands[0].get_line_number(),
+                nlocals: function.commands[0].get_arg2().unwrap_or(0),
+                max_depth,
+                calls,
+            });
+        }
+    }
+
+    usages
+}
+
+fn get_worst_case_usage(
+    function_name: &str, usages: &HashMap<String, FunctionUsage>,
+    known_usages: &mut HashMap<String, i32>, visiting: &mut Vec<String>) -> i32 {
+
+    if let Some(&usage) = known_usages.get(function_name) {
+        return usage;
+    }
+    let Some(usage) = usages.get(function_name) else {
+        return 0;
+    };
+    if visiting.iter().any(|name| name == function_name) {
+        return 0;
+    }
+
+    visiting.push(function_name.to_string());
+    let mut deepest = usage.max_depth;
+    for (callee, depth) in &usage.calls {
+        deepest = deepest.max(depth + get_worst_case_usage(callee, usages, known_usages, visiting));
+    }
+    visiting.pop();
+
+    let worst_case = FRAME_SIZE + usage.nlocals + deepest;
+    known_usages.insert(function_name.to_string(), worst_case);
+    worst_case
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        diagnostics::{registry::{STACK_OVERFLOW, UNBOUNDED_RECURSION}, Diagnostic},
+        program::Program,
+    };
+
+    use super::{check_stack_usage, get_function_usages, get_worst_case_usage};
+
+    #[test]
+    fn test_get_worst_case_usage() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 1\npush constant 1\npush constant 2\ncall Math.add 2\npop local 0\nreturn"),
+            ("Math", "function Math.add 2\npush argument 0\npush argument 1\nadd\nreturn"),
+        ]);
+        let usages = get_function_usages(&program);
+
+        assert_eq!(get_worst_case_usage("Math.add", &usages, &mut HashMap::new(), &mut Vec::new()), 9);
+        assert_eq!(get_worst_case_usage("Sys.init", &usages, &mut HashMap::new(), &mut Vec::new()), 17);
+    }
+
+    #[test]
+    fn test_check_stack_usage_given_recursion() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 0\npush constant 0\ncall Sys.loop 1\nreturn
+                function Sys.loop 0\npush argument 0\ncall Sys.loop 1\nreturn
+                function Sys.unused 0\ncall Sys.unused 0\nreturn"),
+        ]);
+
+        assert_eq!(check_stack_usage(&program, "Sys.init"), vec![
+            Diagnostic::new(&UNBOUNDED_RECURSION, "Sys.vm", 5, String::from(
+                "recursion through 'Sys.loop' makes the worst-case stack usage unbounded")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_stack_usage_given_large_locals() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 0\ncall Main.main 0\nreturn"),
+            ("Main", "function Main.main 1800\npush constant 0\nreturn"),
+        ]);
+
+        assert_eq!(check_stack_usage(&program, "Sys.init"), vec![
+            Diagnostic::new(&STACK_OVERFLOW, "Sys.vm", 1, String::from(
+                "worst-case stack usage from 'Sys.init' is 1811 words, exceeding the 1792 words between RAM 256 and 2047")),
+        ]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::{
+    diagnostics::{
+        registry::{DUPLICATE_FUNCTION, DUPLICATE_LABEL, UNDEFINED_FUNCTION, UNDEFINED_LABEL},
+        Diagnostic,
+    },
+    parser::{Command, CommandType}, program::Program,
+};
+
+
+pub fn check_symbols(program: &Program, is_whole_program: bool) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut definitions: HashMap<&str, (&str, usize)> = HashMap::new();
+
+    for file in &program.files {
+        for function in file.functions() {
+            let line_number = function.commands[0].get_line_number();
+            match definitions.get(function.name) {
+                Some((path, first_line_number)) => diagnostics.push(Diagnostic::new(
+                    &DUPLICATE_FUNCTION,
+                    &file.path,
+                    line_number,
+                    format!("duplicate function '{}' (first defined at {}:{})",
+                        function.name, path, first_line_number),
+                )),
+                None => {
+                    definitions.insert(function.name, (&file.path, line_number));
+                },
+            }
+        }
+    }
+
+    for file in &program.files {
+        diagnostics.extend(check_labels(&file.path, &file.name, file.top_level_commands()));
+        for function in file.functions() {
+            diagnostics.extend(check_labels(&file.path, function.name, function.commands));
+        }
+    }
+
+    if is_whole_program {
+        for file in &program.files {
+            for command in &file.commands {
+                if command.get_command_type() == &CommandType::Call
+                    && !definitions.contains_key(command.get_arg1()) {
+                    diagnostics.push(Diagnostic::new(
+                        &UNDEFINED_FUNCTION,
+                        &file.path,
+                        command.get_line_number(),
+                        format!("call to undefined function '{}'", command.get_arg1()),
+                    ));
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn check_labels(path: &str, scope: &str, commands: &[Command]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+
+    for command in commands {
+        if command.get_command_type() != &CommandType::Label {
+            continue;
+        }
+        match labels.get(command.get_arg1()) {
+            Some(first_line_number) => diagnostics.push(Diagnostic::new(
+                &DUPLICATE_LABEL,
+                path,
+                command.get_line_number(),
+                format!("duplicate label '{}' in '{}' (first defined at line {})",
+                    command.get_arg1(), scope, first_line_number),
+            )),
+            None => {
+                labels.insert(command.get_arg1(), command.get_line_number());
+            },
+        }
+    }
+
+    for command in commands {
+        if matches!(command.get_command_type(), CommandType::Goto | CommandType::If)
+            && !labels.contains_key(command.get_arg1()) {
+            diagnostics.push(Diagnostic::new(
+                &UNDEFINED_LABEL,
+                path,
+                command.get_line_number(),
+                format!("undefined label '{}' in '{}'", command.get_arg1(), scope),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::{
+            registry::{DUPLICATE_FUNCTION, DUPLICATE_LABEL, UNDEFINED_FUNCTION, UNDEFINED_LABEL},
+            Diagnostic,
+        },
+        program::Program,
+    };
+
+    use super::check_symbols;
+
+    #[test]
+    fn test_check_symbols_given_valid_program() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\nlabel LOOP\ncall Math.add 0\ngoto LOOP"),
+            ("Math", "function Math.add 0\nlabel LOOP\nif-goto LOOP\nreturn"),
+        ]);
+
+        assert!(check_symbols(&program, true).is_empty());
+    }
+
+    #[test]
+    fn test_check_symbols_given_undefined_function() {
+        let program = Program::new(&[("Main", "function Main.main 0\ncall Math.add 2\nreturn")]);
+
+        assert_eq!(check_symbols(&program, true), vec![
+            Diagnostic::new(&UNDEFINED_FUNCTION, "Main.vm", 2, String::from("call to undefined function 'Math.add'")),
+        ]);
+        assert!(check_symbols(&program, false).is_empty());
+    }
+
+    #[test]
+    fn test_check_symbols_given_undefined_label() {
+        let program = Program::new(&[
+            ("Main", "goto START\nfunction Main.main 0\nlabel LOOP\nreturn\nfunction Main.test 0\nif-goto LOOP\nreturn"),
+        ]);
+
+        assert_eq!(check_symbols(&program, true), vec![
+            Diagnostic::new(&UNDEFINED_LABEL, "Main.vm", 1, String::from("undefined label 'START' in 'Main'")),
+            Diagnostic::new(&UNDEFINED_LABEL, "Main.vm", 6, String::from("undefined label 'LOOP' in 'Main.test'")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_symbols_given_duplicate_label() {
+        let program = Program::new(&[("Main", "function Main.main 0\nlabel LOOP\nlabel LOOP\nreturn")]);
+
+        assert_eq!(check_symbols(&program, true), vec![
+            Diagnostic::new(
+                &DUPLICATE_LABEL, "Main.vm", 3, String::from("duplicate label 'LOOP' in 'Main.main' (first defined at line 2)")),
+        ]);
+    }
+
+    #[test]
+    fn test_check_symbols_given_duplicate_function() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\nreturn"),
+            ("Other", "\nfunction Main.main 0\nreturn"),
+        ]);
+
+        assert_eq!(check_symbols(&program, true), vec![
+            Diagnostic::new(
+                &DUPLICATE_FUNCTION, "Other.vm", 2, String::from("duplicate function 'Main.main' (first defined at Main.vm:1)")),
+        ]);
+    }
+}
@@ -0,0 +1,137 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CommentLevel {
+    None,
+    #[default]
+    Commands,
+    Full,
+}
+
+pub enum AsmComment<'a> {
+    File(&'a str),
+    Vm { text: &'a str, location: Option<(&'a str, usize)> },
+    Bootstrap,
+    Arithmetic(&'a str),
+    PushPop { command: &'a str, segment: &'a str, index: i32, file_name: &'a str },
+    Function { name: &'a str, nvars: i32 },
+    Label(&'a str),
+    Goto(&'a str),
+    If(&'a str),
+    Call { name: &'a str, nargs: i32 },
+    TailCall { name: &'a str, nargs: i32 },
+    Return,
+}
+
+pub fn format_comment(comment: &AsmComment, level: CommentLevel) -> Option<String> {
+    let text = match comment {
+        AsmComment::File(file_name) => format!("> {}.asm", file_name),
+        AsmComment::Vm { text, location: Some((file_name, line_number)) } => {
+            format!("{}.vm:{}: {}", file_name, line_number, text)
+        },
+        AsmComment::Vm { text, location: None } => text.to_string(),
+        AsmComment::Bootstrap => String::from("bootstrap"),
+        AsmComment::Arithmetic(command) => command.to_string(),
+        AsmComment::PushPop { command, segment, index, .. } => format!("{} {} {}", command, segment, index),
+        AsmComment::Function { name, nvars } => format!("function {} {}", name, nvars),
+        AsmComment::Label(label) => format!("label {}", label),
+        AsmComment::Goto(label) => format!("goto {}", label),
+        AsmComment::If(label) => format!("if {}", label),
+        AsmComment::Call { name, nargs } => format!("call {} {}", name, nargs),
+        AsmComment::TailCall { name, nargs } => format!("tail call {} {}", name, nargs),
+        AsmComment::Return => String::from("return"),
+    };
+
+    match (level, get_annotation(comment)) {
+        (CommentLevel::None, _) => None,
+        (CommentLevel::Full, Some(annotation)) => Some(format!("// {} ({})", text, annotation)),
+        _ => Some(format!("// {}", text)),
+    }
+}
+
+fn get_annotation(comment: &AsmComment) -> Option<String> {
+    let annotation = match comment {
+        AsmComment::File(_) | AsmComment::Vm { .. } => return None,
+        AsmComment::Bootstrap => String::from("SP = 256, then call Sys.init"),
+        AsmComment::Arithmetic(command) => {
+            let (effect, value) = match *command {
+                "add" => (-1, "x + y"),
+                "sub" => (-1, "x - y"),
+                "neg" => (0, "-y"),
+                "eq" => (-1, "x == y ? -1 : 0"),
+                "gt" => (-1, "x > y ? -1 : 0"),
+                "lt" => (-1, "x < y ? -1 : 0"),
+                "and" => (-1, "x & y"),
+                "or" => (-1, "x | y"),
+                _ => (0, "!y"),
+            };
+            format!("stack {}, {}", format_effect(effect), value)
+        },
+        AsmComment::PushPop { command, segment, index, file_name } => {
+            let location = match *segment {
+                "constant" => index.to_string(),
+                "local" => format!("RAM[LCL+{}]", index),
+                "argument" => format!("RAM[ARG+{}]", index),
+                "this" => format!("RAM[THIS+{}]", index),
+                "that" => format!("RAM[THAT+{}]", index),
+                "pointer" if *index == 0 => String::from("THIS"),
+                "pointer" => String::from("THAT"),
+                "temp" => format!("RAM[{}]", 5 + index),
+                _ => format!("{}.{}", file_name, index),
+            };
+            match *command {
+                "push" => format!("stack +1, y = {}", location),
+                _ => format!("stack -1, {} = y", location),
+            }
+        },
+        AsmComment::Function { nvars, .. } => format!("stack {}, {} local(s) set to 0", format_effect(*nvars), nvars),
+        AsmComment::Label(_) | AsmComment::Goto(_) => String::from("stack 0"),
+        AsmComment::If(_) => String::from("stack -1, jump if y != 0"),
+        AsmComment::Call { nargs, .. } => {
+            format!("stack {}, {} argument(s) replaced by the return value", format_effect(1 - nargs), nargs)
+        },
+        AsmComment::TailCall { nargs, .. } => {
+            format!("{} argument(s) moved over the caller's frame, which is reused", nargs)
+        },
+        AsmComment::Return => String::from("frame dropped, y copied to ARG[0]"),
+    };
+    Some(annotation)
+}
+
+fn format_effect(effect: i32) -> String {
+    if effect > 0 {
+        format!("+{}", effect)
+    } else {
+        effect.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_comment, AsmComment, CommentLevel};
+
+    #[test]
+    fn test_format_comment() {
+        let push = AsmComment::PushPop { command: "push", segment: "local", index: 2, file_name: "Main" };
+        assert_eq!(format_comment(&push, CommentLevel::None), None);
+        assert_eq!(format_comment(&push, CommentLevel::Commands), Some(String::from("// push local 2")));
+        assert_eq!(format_comment(&push, CommentLevel::Full),
+            Some(String::from("// push local 2 (stack +1, y = RAM[LCL+2])")));
+    }
+
+    #[test]
+    fn test_format_comment_given_full_level() {
+        let full = |comment| format_comment(&comment, CommentLevel::Full).unwrap();
+
+        assert_eq!(full(AsmComment::File("Main")), "// > Main.asm");
+        assert_eq!(full(AsmComment::Vm { text: "let x = 1", location: Some(("Main", 3)) }), "// Main.vm:3: let x = 1");
+        assert_eq!(full(AsmComment::Arithmetic("lt")), "// lt (stack -1, x < y ? -1 : 0)");
+        assert_eq!(full(AsmComment::PushPop { command: "pop", segment: "static", index: 1, file_name: "Main" }),
+            "// pop static 1 (stack -1, Main.1 = y)");
+        assert_eq!(full(AsmComment::PushPop { command: "push", segment: "temp", index: 2, file_name: "Main" }),
+            "// push temp 2 (stack +1, y = RAM[7])");
+        assert_eq!(full(AsmComment::Function { name: "Main.main", nvars: 0 }),
+            "// function Main.main 0 (stack 0, 0 local(s) set to 0)");
+        assert_eq!(full(AsmComment::Call { name: "Math.add", nargs: 2 }),
+            "// call Math.add 2 (stack -1, 2 argument(s) replaced by the return value)");
+        assert_eq!(full(AsmComment::If("END")), "// if END (stack -1, jump if y != 0)");
+    }
+}
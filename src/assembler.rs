@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+
+const VARIABLE_START: u16 = 16;
+const PREDEFINED_SYMBOLS: [(&str, u16); 7] = [
+    ("SP", 0), ("LCL", 1), ("ARG", 2), ("THIS", 3), ("THAT", 4), ("SCREEN", 16384), ("KBD", 24576),
+];
+const COMPUTATIONS: [(&str, u16); 18] = [
+    ("0", 0b101010), ("1", 0b111111), ("-1", 0b111010), ("D", 0b001100), ("A", 0b110000),
+    ("!D", 0b001101), ("!A", 0b110001), ("-D", 0b001111), ("-A", 0b110011), ("D+1", 0b011111),
+    ("A+1", 0b110111), ("D-1", 0b001110), ("A-1", 0b110010), ("D+A", 0b000010), ("D-A", 0b010011),
+    ("A-D", 0b000111), ("D&A", 0b000000), ("D|A", 0b010101),
+];
+const JUMPS: [&str; 7] = ["JGT", "JEQ", "JGE", "JLT", "JNE", "JLE", "JMP"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AsmLine<'a> {
+    Label(&'a str),
+    Address(&'a str),
+    Compute(&'a str),
+}
+
+impl<'a> AsmLine<'a> {
+    pub fn parse(line: &'a str) -> Option<AsmLine<'a>> {
+        let text = line.split("//").next().unwrap_or_default().trim();
+        if text.is_empty() {
+            None
+        } else if let Some(label) = text.strip_prefix('(').and_then(|text| text.strip_suffix(')')) {
+            Some(AsmLine::Label(label))
+        } else if let Some(value) = text.strip_prefix('@') {
+            Some(AsmLine::Address(value))
+        } else {
+            Some(AsmLine::Compute(text))
+        }
+    }
+}
+
+pub struct SymbolTable {
+    addresses: HashMap<String, u16>,
+}
+
+impl SymbolTable {
+    pub fn new(asm_text: &str) -> SymbolTable {
+        let mut addresses: HashMap<String, u16> = PREDEFINED_SYMBOLS.iter()
+            .map(|(symbol, address)| (symbol.to_string(), *address))
+            .chain((0..16).map(|index| (format!("R{}", index), index)))
+            .collect();
+        let lines: Vec<AsmLine> = asm_text.lines().filter_map(AsmLine::parse).collect();
+
+        let mut rom_address = 0;
+        for line in &lines {
+            match line {
+                AsmLine::Label(label) => {
+                    addresses.insert(label.to_string(), rom_address);
+                },
+                _ => rom_address += 1,
+            }
+        }
+
+        let mut variable_address = VARIABLE_START;
+        for line in &lines {
+            if let AsmLine::Address(symbol) = line {
+                if symbol.parse::<u16>().is_err() && !addresses.contains_key(*symbol) {
+                    addresses.insert(symbol.to_string(), variable_address);
+                    variable_address += 1;
+                }
+            }
+        }
+
+        SymbolTable { addresses }
+    }
+
+    pub fn get_address(&self, symbol: &str) -> Option<u16> {
+        self.addresses.get(symbol).copied()
+    }
+}
+
+pub fn assemble(asm_text: &str) -> Result<Vec<u16>, String> {
+    let symbol_table = SymbolTable::new(asm_text);
+    let mut instructions = Vec::new();
+
+    for (index, line) in asm_text.lines().enumerate() {
+        let instruction = match AsmLine::parse(line) {
+            None | Some(AsmLine::Label(_)) => continue,
+            Some(AsmLine::Address(symbol)) => symbol.parse::<u16>().ok()
+                .filter(|value| *value < 0x8000)
+                .or_else(|| symbol_table.get_address(symbol)),
+            Some(AsmLine::Compute(text)) => encode_compute(text),
+        };
+        match instruction {
+            Some(instruction) => instructions.push(instruction),
+            None => return Err(format!("line {}: invalid instruction '{}'", index + 1, line.trim())),
+        }
+    }
+
+    Ok(instructions)
+}
+
+fn encode_compute(text: &str) -> Option<u16> {
+    let (dest, rest) = text.split_once('=').unwrap_or(("", text));
+    let (comp, jump) = rest.split_once(';').unwrap_or((rest, ""));
+
+    let mut dest_bits = 0;
+    for register in dest.chars() {
+        dest_bits |= match register {
+            'A' => 0b100,
+            'D' => 0b010,
+            'M' => 0b001,
+            _ => return None,
+        };
+    }
+    let jump_bits = match jump {
+        "" => 0,
+        _ => JUMPS.iter().position(|name| *name == jump)? as u16 + 1,
+    };
+    let (a_bit, comp) = if comp.contains('M') { (1, comp.replace('M', "A")) } else { (0, comp.to_string()) };
+    let comp_bits = COMPUTATIONS.iter()
+        .find(|(name, _)| *name == comp || (name.len() == 3 && "+&|".contains(&name[1..2])
+            && name.chars().rev().collect::<String>() == comp))
+        .map(|(_, bits)| *bits)?;
+
+    Some(0b111 << 13 | a_bit << 12 | comp_bits << 6 | dest_bits << 3 | jump_bits)
+}
+
+pub fn get_hack_text(instructions: &[u16]) -> String {
+    instructions.iter().map(|instruction| format!("{:016b}\n", instruction)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assemble, get_hack_text, AsmLine, SymbolTable};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(AsmLine::parse("(LOOP)"), Some(AsmLine::Label("LOOP")));
+        assert_eq!(AsmLine::parse("  @SP // stack"), Some(AsmLine::Address("SP")));
+        assert_eq!(AsmLine::parse("  D;JGT"), Some(AsmLine::Compute("D;JGT")));
+        assert_eq!(AsmLine::parse("// push constant 1"), None);
+    }
+
+    #[test]
+    fn test_symbol_table() {
+        let symbol_table = SymbolTable::new("@Main.0\nD=M\n(LOOP)\n@LOOP\n0;JMP\n@Main.1\n@R13\n@Main.0\n(END)");
+
+        assert_eq!(symbol_table.get_address("LOOP"), Some(2));
+        assert_eq!(symbol_table.get_address("END"), Some(7));
+        assert_eq!(symbol_table.get_address("Main.0"), Some(16));
+        assert_eq!(symbol_table.get_address("Main.1"), Some(17));
+        assert_eq!(symbol_table.get_address("R13"), Some(13));
+        assert_eq!(symbol_table.get_address("THAT"), Some(4));
+    }
+
+    #[test]
+    fn test_assemble() {
+        let instructions = assemble("@256\nD=A\n@SP\nM=D\n(LOOP)\nAM=M-1\nD=D+M\nM=M+D\n@LOOP\n0;JMP\nD;JGT").unwrap();
+
+        assert_eq!(get_hack_text(&instructions), "\
+0000000100000000
+1110110000010000
+0000000000000000
+1110001100001000
+1111110010101000
+1111000010010000
+1111000010001000
+0000000000000100
+1110101010000111
+1110001100000001
+");
+    }
+
+    #[test]
+    fn test_assemble_given_invalid_instruction() {
+        assert_eq!(assemble("@1\nD=D*A"), Err(String::from("line 2: invalid instruction 'D=D*A'")));
+        assert_eq!(assemble("@32768"), Err(String::from("line 1: invalid instruction '@32768'")));
+    }
+}
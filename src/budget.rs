@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::{code_writer::{AsmSymbol, SymbolKind}, source_map::SourceMapping};
+
+
+pub const ROM_SIZE: usize = 32768;
+const STATIC_START: usize = 16;
+const STATIC_END: usize = 255;
+
+pub struct FileUsage {
+    pub vm_file: String,
+    pub size: usize,
+    pub functions: Vec<(String, usize)>,
+}
+
+pub fn check_rom_budget(rom_size: usize, mappings: &[SourceMapping], max_rom: usize) -> Result<(), String> {
+    if rom_size <= max_rom {
+        return Ok(());
+    }
+
+    let mut lines = vec![format!(
+        "ROM budget exceeded: {} instructions were emitted but the budget is {}", rom_size, max_rom)];
+    let mapped_size: usize = mappings.iter().map(|mapping| mapping.rom_end - mapping.rom_start).sum();
+    if rom_size > mapped_size {
+        lines.push(format!("  {:<40}{:>6}", "(generated)", rom_size - mapped_size));
+    }
+    for file_usage in get_rom_usage(mappings) {
+        lines.push(format!("  {:<40}{:>6}", file_usage.vm_file, file_usage.size));
+        for (function_name, size) in file_usage.functions {
+            lines.push(format!("    {:<38}{:>6}", function_name, size));
+        }
+    }
+    Err(lines.join("\n"))
+}
+
+pub fn check_static_budget(symbols: &[AsmSymbol]) -> Result<(), String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for symbol in symbols.iter().filter(|symbol| symbol.kind == SymbolKind::Static) {
+        *counts.entry(symbol.file_name.as_deref().unwrap_or_default()).or_default() += 1;
+    }
+
+    let static_count: usize = counts.values().sum();
+    let max_statics = STATIC_END - STATIC_START + 1;
+    if static_count <= max_statics {
+        return Ok(());
+    }
+
+    let mut lines = vec![format!(
+        "Static budget exceeded: {} static variables were allocated but only {} fit between RAM {} and {}",
+        static_count, max_statics, STATIC_START, STATIC_END)];
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    for (file_name, count) in counts {
+        lines.push(format!("  {:<40}{:>6}", format!("{}.vm", file_name), count));
+    }
+    Err(lines.join("\n"))
+}
+
+pub fn get_rom_usage(mappings: &[SourceMapping]) -> Vec<FileUsage> {
+    let mut sizes: HashMap<&str, HashMap<&str, usize>> = HashMap::new();
+    for mapping in mappings {
+        *sizes.entry(&mapping.vm_file).or_default()
+            .entry(&mapping.function_name).or_default() += mapping.rom_end - mapping.rom_start;
+    }
+
+    let mut usages: Vec<FileUsage> = sizes.into_iter()
+        .map(|(vm_file, function_sizes)| {
+            let mut functions: Vec<(String, usize)> = function_sizes.into_iter()
+                .map(|(function_name, size)| {
+                    let name = if function_name.is_empty() { "(top level)" } else { function_name };
+                    (name.to_string(), size)
+                })
+                .collect();
+            functions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            FileUsage {
+                vm_file: vm_file.to_string(),
+                size: functions.iter().map(|(_, size)| size).sum(),
+                functions,
+            }
+        })
+        .collect();
+
+    usages.sort_by(|a, b| b.size.cmp(&a.size).then(a.vm_file.cmp(&b.vm_file)));
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{code_writer::{AsmSymbol, SymbolKind}, source_map::SourceMapping};
+
+    use super::{check_rom_budget, check_static_budget};
+
+    fn new_mapping(vm_file: &str, function_name: &str, rom_start: usize, rom_end: usize) -> SourceMapping {
+        SourceMapping {
+            vm_file: vm_file.to_string(),
+            line_number: 1,
+            function_name: function_name.to_string(),
+            asm_start_line: 1,
+            asm_end_line: 1,
+            rom_start,
+            rom_end,
+        }
+    }
+
+    #[test]
+    fn test_check_rom_budget() {
+        let mappings = vec![
+            new_mapping("Main.vm", "Main.main", 10, 20),
+            new_mapping("Math.vm", "Math.add", 20, 50),
+            new_mapping("Main.vm", "Main.helper", 50, 65),
+            new_mapping("Main.vm", "Main.main", 65, 70),
+        ];
+
+        assert!(check_rom_budget(70, &mappings, 70).is_ok());
+        assert_eq!(check_rom_budget(70, &mappings, 64), Err(String::from("\
+ROM budget exceeded: 70 instructions were emitted but the budget is 64
+  (generated)                                 10
+  Main.vm                                     30
+    Main.helper                               15
+    Main.main                                 15
+  Math.vm                                     30
+    Math.add                                  30")));
+    }
+
+    #[test]
+    fn test_check_static_budget() {
+        let symbols: Vec<AsmSymbol> = (0..241)
+            .map(|index| AsmSymbol {
+                name: format!("Main.{}", index),
+                kind: SymbolKind::Static,
+                file_name: Some(String::from(if index < 200 { "Main" } else { "Math" })),
+            })
+            .collect();
+
+        assert!(check_static_budget(&symbols[..240]).is_ok());
+        assert_eq!(check_static_budget(&symbols), Err(String::from("\
+Static budget exceeded: 241 static variables were allocated but only 240 fit between RAM 16 and 255
+  Main.vm                                    200
+  Math.vm                                     41")));
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{parser::{Command, CommandType}, program::Program};
+
+
+pub struct CallGraph {
+    functions: Vec<String>,
+    callees: HashMap<String, Vec<String>>,
+    top_level_callees: Vec<String>,
+}
+
+impl CallGraph {
+    pub fn new(program: &Program) -> CallGraph {
+        let mut functions = Vec::new();
+        let mut callees = HashMap::new();
+        let mut top_level_callees = Vec::new();
+
+        for file in &program.files {
+            CallGraph::add_callees(&mut top_level_callees, file.top_level_commands());
+
+            for function in file.functions() {
+                functions.push(function.name.to_string());
+                let function_callees: &mut Vec<String> = callees
+                    .entry(function.name.to_string())
+                    .or_default();
+                CallGraph::add_callees(function_callees, function.commands);
+            }
+        }
+
+        CallGraph {
+            functions,
+            callees,
+            top_level_callees,
+        }
+    }
+
+    fn add_callees(callees: &mut Vec<String>, commands: &[Command]) {
+        for command in commands {
+            if command.get_command_type() == &CommandType::Call
+                && !callees.iter().any(|callee| callee == command.get_arg1()) {
+                callees.push(command.get_arg1().to_string());
+            }
+        }
+    }
+
+    pub fn is_defined(&self, function_name: &str) -> bool {
+        self.callees.contains_key(function_name)
+    }
+
+    pub fn callees(&self, function_name: &str) -> &[String] {
+        match self.callees.get(function_name) {
+            Some(callees) => callees,
+            None => &[],
+        }
+    }
+
+    pub fn top_level_callees(&self) -> &[String] {
+        &self.top_level_callees
+    }
+
+    pub fn reachable_from(&self, roots: &[String]) -> HashSet<String> {
+        let mut reachable = HashSet::new();
+        let mut pending: Vec<&str> = roots.iter().map(String::as_str).collect();
+
+        while let Some(function_name) = pending.pop() {
+            if reachable.insert(function_name.to_string()) {
+                pending.extend(self.callees(function_name).iter().map(String::as_str));
+            }
+        }
+
+        reachable
+    }
+
+    pub fn live_functions(&self, entry_function: &str, keep_functions: &[String]) -> Option<HashSet<String>> {
+        if !self.is_defined(entry_function) {
+            return None;
+        }
+
+        let mut roots = vec![entry_function.to_string()];
+        roots.extend(keep_functions.iter().cloned());
+        roots.extend(self.top_level_callees().iter().cloned());
+        Some(self.reachable_from(&roots))
+    }
+
+    pub fn recursive_cycles(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan {
+            call_graph: self,
+            index: 0,
+            indices: HashMap::new(),
+            low_links: HashMap::new(),
+            stack: Vec::new(),
+            components: Vec::new(),
+        };
+        for function_name in &self.functions {
+            if !tarjan.indices.contains_key(function_name.as_str()) {
+                tarjan.visit(function_name);
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = tarjan.components.into_iter()
+            .filter(|component| component.len() > 1
+                || self.callees(&component[0]).contains(&component[0]))
+            .map(|mut component| {
+                component.sort_by_key(|function_name| self.get_function_index(function_name));
+                component
+            })
+            .collect();
+        cycles.sort_by_key(|cycle| self.get_function_index(&cycle[0]));
+        cycles
+    }
+
+    pub fn to_dot(&self, reachable: Option<&HashSet<String>>) -> String {
+        let cycles = self.recursive_cycles();
+        let find_cycle = |function_name: &str| cycles.iter()
+            .position(|cycle| cycle.iter().any(|name| name == function_name));
+
+        let mut lines = vec![String::from("digraph callgraph {"), String::from("    node [shape=box];")];
+        for function_name in &self.functions {
+            let mut attributes = Vec::new();
+            if find_cycle(function_name).is_some() {
+                attributes.push("color=red");
+            }
+            if reachable.is_some_and(|reachable| !reachable.contains(function_name)) {
+                attributes.push("style=dashed");
+                attributes.push("fontcolor=gray");
+            }
+            lines.push(CallGraph::get_dot_node(function_name, &attributes));
+        }
+
+        let mut external_callees: Vec<&str> = Vec::new();
+        for function_name in &self.functions {
+            for callee in self.callees(function_name) {
+                if !self.is_defined(callee) && !external_callees.contains(&callee.as_str()) {
+                    external_callees.push(callee);
+                }
+            }
+        }
+        for callee in external_callees {
+            lines.push(CallGraph::get_dot_node(callee, &["shape=ellipse", "style=dotted"]));
+        }
+
+        for function_name in &self.functions {
+            for callee in self.callees(function_name) {
+                let is_recursive = find_cycle(function_name).is_some()
+                    && find_cycle(function_name) == find_cycle(callee);
+                let attributes = if is_recursive { " [color=red]" } else { "" };
+                lines.push(format!("    \"{}\" -> \"{}\"{};", function_name, callee, attributes));
+            }
+        }
+        lines.push(String::from("}"));
+        lines.join("\n") + "\n"
+    }
+
+    fn get_dot_node(function_name: &str, attributes: &[&str]) -> String {
+        if attributes.is_empty() {
+            format!("    \"{}\";", function_name)
+        } else {
+            format!("    \"{}\" [{}];", function_name, attributes.join(", "))
+        }
+    }
+
+    fn get_function_index(&self, function_name: &str) -> usize {
+        self.functions.iter().position(|name| name == function_name).unwrap_or(usize::MAX)
+    }
+}
+
+struct Tarjan<'a> {
+    call_graph: &'a CallGraph,
+    index: usize,
+    indices: HashMap<&'a str, usize>,
+    low_links: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, function_name: &'a str) {
+        self.indices.insert(function_name, self.index);
+        self.low_links.insert(function_name, self.index);
+        self.index += 1;
+        self.stack.push(function_name);
+
+        for callee in self.call_graph.callees(function_name) {
+            if !self.call_graph.is_defined(callee) {
+                continue;
+            }
+            if !self.indices.contains_key(callee.as_str()) {
+                self.visit(callee);
+                let low_link = self.low_links[function_name].min(self.low_links[callee.as_str()]);
+                self.low_links.insert(function_name, low_link);
+            } else if self.stack.contains(&callee.as_str()) {
+                let low_link = self.low_links[function_name].min(self.indices[callee.as_str()]);
+                self.low_links.insert(function_name, low_link);
+            }
+        }
+
+        if self.low_links[function_name] == self.indices[function_name] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                component.push(member.to_string());
+                if member == function_name {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::Program;
+
+    use super::CallGraph;
+
+    #[test]
+    fn test_callees() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Math.add 2\ncall Math.add 2\ncall Output.print 1\nreturn"),
+            ("Math", "function Math.add 0\nreturn"),
+        ]);
+        let call_graph = CallGraph::new(&program);
+
+        assert_eq!(call_graph.callees("Main.main"), ["Math.add", "Output.print"]);
+        assert!(call_graph.callees("Math.add").is_empty());
+        assert!(call_graph.is_defined("Math.add"));
+        assert!(!call_graph.is_defined("Output.print"));
+    }
+
+    #[test]
+    fn test_top_level_callees() {
+        let program = Program::new(&[("Main", "call Main.main 0\nfunction Main.main 0\nreturn")]);
+        let call_graph = CallGraph::new(&program);
+
+        assert_eq!(call_graph.top_level_callees(), ["Main.main"]);
+    }
+
+    #[test]
+    fn test_reachable_from() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 0\ncall Main.main 0\nreturn"),
+            ("Main", "function Main.main 0\ncall Main.loop 0\nreturn\nfunction Main.loop 0\ncall Main.loop 0\nreturn\nfunction Main.unused 0\nreturn"),
+        ]);
+        let call_graph = CallGraph::new(&program);
+        let reachable = call_graph.reachable_from(&[String::from("Sys.init")]);
+
+        assert_eq!(reachable.len(), 3);
+        assert!(reachable.contains("Main.loop"));
+        assert!(!reachable.contains("Main.unused"));
+    }
+
+    #[test]
+    fn test_live_functions() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 0\ncall Main.main 0\nreturn"),
+            ("Main", "call Main.start 0\nfunction Main.main 0\nreturn\nfunction Main.start 0\nreturn
+                function Main.handler 0\nreturn\nfunction Main.unused 0\nreturn"),
+        ]);
+        let call_graph = CallGraph::new(&program);
+        let mut live_functions: Vec<String> = call_graph
+            .live_functions("Sys.init", &[String::from("Main.handler")])
+            .unwrap()
+            .into_iter()
+            .collect();
+        live_functions.sort();
+
+        assert_eq!(live_functions, ["Main.handler", "Main.main", "Main.start", "Sys.init"]);
+        assert_eq!(call_graph.live_functions("Main.init", &[]), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let program = Program::new(&[
+            ("Sys", "function Sys.init 0\ncall Main.main 0\nreturn"),
+            ("Main", "function Main.main 0\ncall Main.fact 1\ncall Output.print 1\nreturn
+                function Main.fact 0\ncall Main.fact 1\nreturn
+                function Main.unused 0\ncall Main.main 0\nreturn"),
+        ]);
+        let call_graph = CallGraph::new(&program);
+        let reachable = call_graph.reachable_from(&[String::from("Sys.init")]);
+
+        assert_eq!(call_graph.to_dot(Some(&reachable)), "\
+digraph callgraph {
+    node [shape=box];
+    \"Sys.init\";
+    \"Main.main\";
+    \"Main.fact\" [color=red];
+    \"Main.unused\" [style=dashed, fontcolor=gray];
+    \"Output.print\" [shape=ellipse, style=dotted];
+    \"Sys.init\" -> \"Main.main\";
+    \"Main.main\" -> \"Main.fact\";
+    \"Main.main\" -> \"Output.print\";
+    \"Main.fact\" -> \"Main.fact\" [color=red];
+    \"Main.unused\" -> \"Main.main\";
+}
+");
+    }
+
+    #[test]
+    fn test_recursive_cycles() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Main.even 1\ncall Main.fact 1\nreturn
+                function Main.fact 0\ncall Main.fact 1\nreturn
+                function Main.odd 0\ncall Main.even 1\nreturn
+                function Main.even 0\ncall Main.odd 1\nreturn"),
+        ]);
+        let call_graph = CallGraph::new(&program);
+
+        assert_eq!(call_graph.recursive_cycles(), vec![
+            vec![String::from("Main.fact")],
+            vec![String::from("Main.odd"), String::from("Main.even")],
+        ]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::{parser::{Command, CommandType}, program::Program};
+
+
+pub struct BasicBlock {
+    pub start: usize,
+    pub end: usize,
+    pub successors: Vec<usize>,
+}
+
+pub struct ControlFlowGraph<'a> {
+    commands: &'a [Command],
+    blocks: Vec<BasicBlock>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    pub fn new(commands: &'a [Command]) -> ControlFlowGraph<'a> {
+        let mut starts = vec![0];
+        for (index, command) in commands.iter().enumerate() {
+            match command.get_command_type() {
+                CommandType::Label => starts.push(index),
+                CommandType::Goto | CommandType::If | CommandType::Return | CommandType::Call => {
+                    starts.push(index + 1)
+                },
+                _ => {},
+            }
+        }
+        starts.retain(|&start| start < commands.len());
+        starts.dedup();
+
+        let label_blocks: HashMap<&str, usize> = starts.iter()
+            .enumerate()
+            .filter(|(_, &start)| commands[start].get_command_type() == &CommandType::Label)
+            .map(|(block_index, &start)| (commands[start].get_arg1(), block_index))
+            .collect();
+
+        let blocks = starts.iter()
+            .enumerate()
+            .map(|(block_index, &start)| {
+                let end = starts.get(block_index + 1).copied().unwrap_or(commands.len());
+                let next = Some(block_index + 1).filter(|&next| next < starts.len());
+                let last = &commands[end - 1];
+                let target = label_blocks.get(last.get_arg1()).copied();
+                let successors = match last.get_command_type() {
+                    CommandType::Goto => target.into_iter().collect(),
+                    CommandType::If => next.into_iter().chain(target).collect(),
+                    CommandType::Return => Vec::new(),
+                    _ => next.into_iter().collect(),
+                };
+                BasicBlock { start, end, successors }
+            })
+            .collect();
+
+        ControlFlowGraph { commands, blocks }
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    pub fn block_commands(&self, block: &BasicBlock) -> &'a [Command] {
+        &self.commands[block.start..block.end]
+    }
+
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut lines = vec![
+            format!("digraph \"{}\" {{", name),
+            String::from("    node [shape=box, fontname=monospace];"),
+        ];
+        for (block_index, block) in self.blocks.iter().enumerate() {
+            let label: String = self.block_commands(block).iter()
+                .map(|command| format!("{}\\l", command))
+                .collect();
+            lines.push(format!("    b{} [label=\"{}\"];", block_index, label));
+        }
+        for (block_index, block) in self.blocks.iter().enumerate() {
+            let is_branch = self.commands[block.end - 1].get_command_type() == &CommandType::If;
+            for (successor_index, successor) in block.successors.iter().enumerate() {
+                let attributes = match (is_branch, successor_index) {
+                    (false, _) => "",
+                    (true, 0) => " [label=\"false\"]",
+                    (true, _) => " [label=\"true\"]",
+                };
+                lines.push(format!("    b{} -> b{}{};", block_index, successor, attributes));
+            }
+        }
+        lines.push(String::from("}"));
+        lines.join("\n") + "\n"
+    }
+}
+
+pub fn get_program_dot(program: &Program) -> String {
+    program.files.iter()
+        .flat_map(|file| file.functions())
+        .map(|function| ControlFlowGraph::new(function.commands).to_dot(function.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Command;
+
+    use super::ControlFlowGraph;
+
+    fn new_commands(text: &str) -> Vec<Command> {
+        text.lines().map(|line| Command::new(line.trim())).collect()
+    }
+
+    #[test]
+    fn test_blocks() {
+        let commands = new_commands("function Main.loop 0
+            label LOOP
+            push argument 0
+            if-goto END
+            call Main.step 0
+            pop temp 0
+            goto LOOP
+            label END
+            push constant 0
+            return");
+        let cfg = ControlFlowGraph::new(&commands);
+
+        let blocks: Vec<(usize, usize, &[usize])> = cfg.blocks().iter()
+            .map(|block| (block.start, block.end, block.successors.as_slice()))
+            .collect();
+        assert_eq!(blocks, vec![
+            (0, 1, [1].as_slice()),
+            (1, 4, &[2, 4]),
+            (4, 5, &[3]),
+            (5, 7, &[1]),
+            (7, 10, &[]),
+        ]);
+        assert_eq!(cfg.block_commands(&cfg.blocks()[4])[1], Command::new("push constant 0"));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let commands = new_commands("function Main.sign 0
+            push argument 0
+            if-goto POSITIVE
+            push constant 0
+            return
+            label POSITIVE
+            push constant 1
+            return");
+        let cfg = ControlFlowGraph::new(&commands);
+
+        assert_eq!(cfg.to_dot("Main.sign"), "\
+digraph \"Main.sign\" {
+    node [shape=box, fontname=monospace];
+    b0 [label=\"function Main.sign 0\\lpush argument 0\\lif-goto POSITIVE\\l\"];
+    b1 [label=\"push constant 0\\lreturn\\l\"];
+    b2 [label=\"label POSITIVE\\lpush constant 1\\lreturn\\l\"];
+    b0 -> b1 [label=\"false\"];
+    b0 -> b2 [label=\"true\"];
+}
+");
+    }
+}
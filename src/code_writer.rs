@@ -1,5 +1,34 @@
-use std::{fs::File, path::Path, io::Write, collections::HashMap};
+use std::{fs::File, path::Path, io::Write, collections::{HashMap, HashSet}};
 
+use crate::{
+    asm_comment::{format_comment, AsmComment, CommentLevel}, parser::{Command, CommandType},
+    source_map::SourceMapping,
+};
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymbolKind {
+    Static,
+    Function,
+    Return,
+    Label,
+    Branch,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsmSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub file_name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandStat {
+    pub vm_file: String,
+    pub function_name: String,
+    pub kind: &'static str,
+    pub instruction_count: usize,
+}
 
 pub struct CodeWriter {
     file: File,
@@ -12,6 +41,18 @@ pub struct CodeWriter {
     final_push: Vec<String>,
     jump_symbol_table: HashMap<String, String>,
     segment_symbol_table: HashMap<String, String>,
+    compact_frame_functions: HashSet<String>,
+    saved_instruction_count: usize,
+    current_line_number: usize,
+    asm_line_count: usize,
+    rom_address: usize,
+    source_mappings: Vec<SourceMapping>,
+    symbols: Vec<AsmSymbol>,
+    is_writing_bootstrap: bool,
+    command_stats: Vec<CommandStat>,
+    comment_level: CommentLevel,
+    vm_comments: bool,
+    comment_locations: bool,
 }
 
 impl CodeWriter {
@@ -52,57 +93,222 @@ impl CodeWriter {
                 (String::from("argument"), String::from("ARG")),
                 (String::from("this"), String::from("THIS")),
                 (String::from("that"), String::from("THAT")),
-            ])
+            ]),
+            compact_frame_functions: HashSet::new(),
+            saved_instruction_count: 0,
+            current_line_number: 0,
+            asm_line_count: 0,
+            rom_address: 0,
+            source_mappings: Vec::new(),
+            symbols: Vec::new(),
+            is_writing_bootstrap: false,
+            command_stats: Vec::new(),
+            comment_level: CommentLevel::default(),
+            vm_comments: false,
+            comment_locations: false,
+        }
+    }
+
+    pub fn set_compact_frame_functions(&mut self, functions: HashSet<String>) {
+        self.compact_frame_functions = functions;
+    }
+
+    pub fn set_comment_level(&mut self, comment_level: CommentLevel) {
+        self.comment_level = comment_level;
+    }
+
+    pub fn set_vm_comments(&mut self, vm_comments: bool, comment_locations: bool) {
+        self.vm_comments = vm_comments;
+        self.comment_locations = comment_locations;
+    }
+
+    pub fn get_saved_instruction_count(&self) -> usize {
+        self.saved_instruction_count
+    }
+
+    pub fn get_source_mappings(&self) -> &[SourceMapping] {
+        &self.source_mappings
+    }
+
+    pub fn get_rom_size(&self) -> usize {
+        self.rom_address
+    }
+
+    pub fn get_command_stats(&self) -> &[CommandStat] {
+        &self.command_stats
+    }
+
+    pub fn get_symbols(&self) -> &[AsmSymbol] {
+        &self.symbols
+    }
+
+    pub fn set_line_number(&mut self, line_number: usize) {
+        self.current_line_number = line_number;
+    }
+
+    pub fn set_file_name(&mut self, file_name: &str) {
+        self.file_base_name = file_name.to_string();
+        self.current_function_name = String::from("");
+        self.current_line_number = 0;
+        self.branch_index = 1;
+        self.return_index = 1;
+        let statements = self.get_comment(AsmComment::File(file_name));
+        self.write_statements(statements);
+    }
+
+    pub fn write_command(&mut self, command: &Command) {
+        let arg1 = command.get_arg1();
+        self.set_line_number(command.get_line_number());
+        if command.get_command_type() == &CommandType::Function {
+            // the comments above a function belong to its source mapping
+            self.current_function_name = arg1.to_string();
+        }
+        self.write_vm_comments(command);
+        let arg2 = || command.get_arg2().expect("Not available");
+        let rom_start = self.rom_address;
+
+        let kind = match command.get_command_type() {
+            CommandType::Arithmetic if matches!(arg1, "eq" | "gt" | "lt") => "comparison",
+            CommandType::Arithmetic => "arithmetic",
+            CommandType::Push => "push",
+            CommandType::Pop => "pop",
+            CommandType::Label => "label",
+            CommandType::Goto => "goto",
+            CommandType::If => "if-goto",
+            CommandType::Function => "function",
+            CommandType::Call => "call",
+            CommandType::Return => "return",
+        };
+        match command.get_command_type() {
+            CommandType::Arithmetic => self.write_arithmetic(arg1),
+            CommandType::Push => self.write_push_pop("push", arg1, arg2()),
+            CommandType::Pop => self.write_push_pop("pop", arg1, arg2()),
+            CommandType::Label => self.write_label(arg1),
+            CommandType::Goto => self.write_goto(arg1),
+            CommandType::If => self.write_if(arg1),
+            CommandType::Function => self.write_function(arg1, arg2()),
+            CommandType::Call => self.write_call(arg1, arg2()),
+            CommandType::Return => self.write_return(),
         }
+        self.add_command_stat(kind, rom_start);
+    }
+
+    pub fn write_vm_comments(&mut self, command: &Command) {
+        if !self.vm_comments {
+            return;
+        }
+        let statements = command.get_comments().iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .flat_map(|(line_number, text)| {
+                let location = self.comment_locations.then_some((self.file_base_name.as_str(), *line_number));
+                self.get_comment(AsmComment::Vm { text: text.trim(), location })
+            })
+            .collect();
+        self.write_statements(statements);
+    }
+
+    fn get_comment(&self, comment: AsmComment) -> Vec<String> {
+        format_comment(&comment, self.comment_level).into_iter().collect()
+    }
+
+    fn add_command_stat(&mut self, kind: &'static str, rom_start: usize) {
+        self.command_stats.push(CommandStat {
+            vm_file: format!("{}.vm", self.file_base_name),
+            function_name: self.current_function_name.clone(),
+            kind,
+            instruction_count: self.rom_address - rom_start,
+        });
     }
 
     pub fn write_arithmetic(&mut self, command: &str) {
-        let statements: Vec<String>;
-        match command {
+        let statements = match command {
             "add" => {
-                statements = self.get_binary_input_asm("add", vec![String::from("D=D+M")])   
+                self.get_binary_input_asm("add", vec![String::from("D=D+M")])
             }
             "sub" => {
-                statements = self.get_binary_input_asm("sub", vec![String::from("D=M-D")])   
+                self.get_binary_input_asm("sub", vec![String::from("D=M-D")])
             }
             "and" => {
-                statements = self.get_binary_input_asm("and", vec![String::from("D=D&M")])   
+                self.get_binary_input_asm("and", vec![String::from("D=D&M")])
             }
             "or" => {
-                statements = self.get_binary_input_asm("or", vec![String::from("D=D|M")])   
+                self.get_binary_input_asm("or", vec![String::from("D=D|M")])
             }
             "neg" => {
-                statements = self.get_unary_input_asm("neg", vec![String::from("D=-D")])   
+                self.get_unary_input_asm("neg", vec![String::from("D=-D")])
             }
             "not" => {
-                statements = self.get_unary_input_asm("not", vec![String::from("D=!D")])   
+                self.get_unary_input_asm("not", vec![String::from("D=!D")])
             }
             "eq" => {
                 let command_statements = self.get_comparison_asm("eq");
-                statements = self.get_binary_input_asm("eq", command_statements)
+                self.get_binary_input_asm("eq", command_statements)
             }
             "gt" => {
                 let command_statements = self.get_comparison_asm("gt");
-                statements = self.get_binary_input_asm("gt", command_statements)
+                self.get_binary_input_asm("gt", command_statements)
             }
             "lt" => {
                 let command_statements = self.get_comparison_asm("lt");
-                statements = self.get_binary_input_asm("lt", command_statements)
+                self.get_binary_input_asm("lt", command_statements)
             }
             _ => {
-                statements = Vec::new()
+                Vec::new()
             }
-        }
+        };
         self.write_statements(statements);
     }
 
     fn write_statements(&mut self, statements: Vec<String>) {
         let lines: Vec<String> = statements.iter().map(CodeWriter::post_process).collect();
-        for line in lines {
-            self.file.write_all(line.as_bytes()).unwrap()
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            self.file.write_all(line.as_bytes()).unwrap();
+            let is_instruction = line.starts_with("  ");
+            self.asm_line_count += 1;
+            self.rom_address += usize::from(is_instruction);
+            self.add_source_mapping(is_instruction);
         }
     }
 
+    fn add_source_mapping(&mut self, is_instruction: bool) {
+        if self.current_line_number == 0 {
+            return;
+        }
+
+        let vm_file = format!("{}.vm", self.file_base_name);
+        if let Some(mapping) = self.source_mappings.last_mut() {
+            if mapping.vm_file == vm_file
+                && mapping.line_number == self.current_line_number
+                && mapping.function_name == self.current_function_name
+                && mapping.asm_end_line + 1 == self.asm_line_count {
+                mapping.asm_end_line = self.asm_line_count;
+                mapping.rom_end = self.rom_address;
+                return;
+            }
+        }
+
+        self.source_mappings.push(SourceMapping {
+            vm_file,
+            line_number: self.current_line_number,
+            function_name: self.current_function_name.clone(),
+            asm_start_line: self.asm_line_count,
+            asm_end_line: self.asm_line_count,
+            rom_start: self.rom_address - usize::from(is_instruction),
+            rom_end: self.rom_address,
+        });
+    }
+
+    fn add_symbol(&mut self, name: &str, kind: SymbolKind) {
+        if kind == SymbolKind::Static && self.symbols.iter().any(|symbol| symbol.name == name) {
+            return;
+        }
+        self.symbols.push(AsmSymbol {
+            name: name.to_string(),
+            kind,
+            file_name: (!self.is_writing_bootstrap).then(|| self.file_base_name.clone()),
+        });
+    }
+
     fn post_process(statement: &String) -> String {
         if let Some(first_char) = statement.chars().next() {
             match first_char {
@@ -117,7 +323,7 @@ impl CodeWriter {
     fn get_binary_input_asm(
         &self, command_name: &str, command_statements: Vec<String>) -> Vec<String> {
 
-        let mut statements = vec![format!("// {}", command_name)];
+        let mut statements = self.get_comment(AsmComment::Arithmetic(command_name));
         statements.extend(self.first_pop.clone());
         statements.extend(self.second_pop.clone());
         statements.extend(command_statements);
@@ -128,7 +334,7 @@ impl CodeWriter {
     fn get_unary_input_asm(
         &self, command_name: &str, command_statements: Vec<String>) -> Vec<String> {
         
-        let mut statements = vec![format!("// {}", command_name)];
+        let mut statements = self.get_comment(AsmComment::Arithmetic(command_name));
         statements.extend(self.first_pop.clone());
         statements.extend(command_statements);
         statements.extend(self.final_push.clone());
@@ -136,16 +342,20 @@ impl CodeWriter {
     }
 
     fn get_comparison_asm(&mut self, command: &str) -> Vec<String> {
+        let then_label = format!("{}_THEN{}", self.get_label_prefix(), self.branch_index);
+        let end_label = format!("{}_END{}", self.get_label_prefix(), self.branch_index);
+        self.add_symbol(&then_label, SymbolKind::Branch);
+        self.add_symbol(&end_label, SymbolKind::Branch);
         let statements = vec![
             String::from("D=M-D"),
-            format!("@{}_THEN{}", self.get_label_prefix(), self.branch_index),
+            format!("@{}", then_label),
             format!("D;{}", self.jump_symbol_table[command]),
             String::from("D=0"),
-            format!("@{}_END{}", self.get_label_prefix(), self.branch_index),
+            format!("@{}", end_label),
             String::from("0;JMP"),
-            format!("({}_THEN{})", self.get_label_prefix(), self.branch_index),
+            format!("({})", then_label),
             String::from("D=-1"),
-            format!("({}_END{})", self.get_label_prefix(), self.branch_index),
+            format!("({})", end_label),
         ];
         self.branch_index += 1;
         statements
@@ -160,31 +370,44 @@ impl CodeWriter {
     }
 
     pub fn write_push_pop(&mut self, command: &str, segment: &str, index: i32) {
-        let mut statements = vec![format!("// {} {} {}", command, segment, index)];
+        let mut statements = self.get_comment(AsmComment::PushPop {
+            command, segment, index, file_name: &self.file_base_name,
+        });
 
         match (command, segment, index) {
             ("push", "local" | "argument" | "this" | "that", index) => {
-                statements.push(format!("@{}", self.segment_symbol_table[segment]));
-                statements.push(String::from("D=M"));
-                statements.push(format!("@{}", index));
-                statements.push(String::from("A=D+A"));
+                let generic = vec![
+                    format!("@{}", self.segment_symbol_table[segment]),
+                    String::from("D=M"),
+                    format!("@{}", index),
+                    String::from("A=D+A"),
+                ];
+                let unrolled = self.get_unrolled_address_asm(segment, index);
+                statements.extend(CodeWriter::get_shorter_asm(generic, unrolled));
                 statements.push(String::from("D=M"));
                 statements.extend(self.final_push.clone());
             },
             ("pop", "local" | "argument" | "this" | "that", index) => {
-                statements.push(format!("@{}", self.segment_symbol_table[segment]));
-                statements.push(String::from("D=M"));
-                statements.push(format!("@{}", index));
-                statements.push(String::from("D=D+A"));
-                statements.push(String::from("@R13"));
-                statements.push(String::from("M=D"));
-                statements.push(String::from("@SP"));
-                statements.push(String::from("M=M-1"));
-                statements.push(String::from("A=M"));
-                statements.push(String::from("D=M"));
-                statements.push(String::from("@R13"));
-                statements.push(String::from("A=M"));
-                statements.push(String::from("M=D"));
+                let mut generic = vec![
+                    format!("@{}", self.segment_symbol_table[segment]),
+                    String::from("D=M"),
+                    format!("@{}", index),
+                    String::from("D=D+A"),
+                    String::from("@R13"),
+                    String::from("M=D"),
+                ];
+                generic.extend(self.first_pop.clone());
+                generic.extend(vec![
+                    String::from("@R13"),
+                    String::from("A=M"),
+                    String::from("M=D"),
+                ]);
+
+                let mut unrolled = self.first_pop.clone();
+                unrolled.extend(self.get_unrolled_address_asm(segment, index));
+                unrolled.push(String::from("M=D"));
+
+                statements.extend(CodeWriter::get_shorter_asm(generic, unrolled));
             },
             ("push", "pointer", 0) => {
                 statements.push(String::from("@THIS"));
@@ -233,13 +456,17 @@ impl CodeWriter {
                 statements.extend(self.final_push.clone());
             },
             ("push", "static", index) => {
-                statements.push(format!("@{}.{}", &self.file_base_name, index));
+                let static_symbol = format!("{}.{}", &self.file_base_name, index);
+                self.add_symbol(&static_symbol, SymbolKind::Static);
+                statements.push(format!("@{}", static_symbol));
                 statements.push(String::from("D=M"));
                 statements.extend(self.final_push.clone());
             },
             ("pop", "static", index) => {
+                let static_symbol = format!("{}.{}", &self.file_base_name, index);
+                self.add_symbol(&static_symbol, SymbolKind::Static);
                 statements.extend(self.first_pop.clone());
-                statements.push(format!("@{}.{}", &self.file_base_name, index));
+                statements.push(format!("@{}", static_symbol));
                 statements.push(String::from("M=D"));
             },
             _ => {
@@ -249,15 +476,37 @@ impl CodeWriter {
         self.write_statements(statements);
     }
 
+    fn get_unrolled_address_asm(&self, segment: &str, index: i32) -> Vec<String> {
+        let mut statements = vec![format!("@{}", self.segment_symbol_table[segment])];
+        if index == 0 {
+            statements.push(String::from("A=M"));
+        } else {
+            statements.push(String::from("A=M+1"));
+            for _ in 1..index {
+                statements.push(String::from("A=A+1"));
+            }
+        }
+        statements
+    }
+
+    fn get_shorter_asm(generic: Vec<String>, unrolled: Vec<String>) -> Vec<String> {
+        if unrolled.len() < generic.len() {
+            unrolled
+        } else {
+            generic
+        }
+    }
+
     pub fn write_function(&mut self, function_name: &str, nvars: i32) {
-        let mut statements = vec![
-            format!("// function {} {}", function_name, nvars),
-            format!("({})", function_name),
-        ];
+        let mut statements = self.get_comment(AsmComment::Function { name: function_name, nvars });
+        statements.push(format!("({})", function_name));
         statements.extend(self.get_push_nvars_asm(nvars));
 
+        self.add_symbol(function_name, SymbolKind::Function);
+        self.current_function_name = function_name.to_string();
+        self.branch_index = 1;
+        self.return_index = 1;
         self.write_statements(statements);
-        self.current_function_name = function_name.to_string()
     }
 
     fn get_push_nvars_asm(&self, nvars: i32) -> Vec<String> {
@@ -278,24 +527,24 @@ impl CodeWriter {
     }
 
     pub fn write_label(&mut self, label: &str) {
-        let statements = vec![
-            format!("// label {}", label),
-            format!("({}${})", self.get_label_prefix(), label),
-        ];
+        let asm_label = format!("{}${}", self.get_label_prefix(), label);
+        self.add_symbol(&asm_label, SymbolKind::Label);
+        let mut statements = self.get_comment(AsmComment::Label(label));
+        statements.push(format!("({})", asm_label));
         self.write_statements(statements);
     }
 
     pub fn write_goto(&mut self, label: &str) {
-        let statements = vec![
-            format!("// goto {}", label),
+        let mut statements = self.get_comment(AsmComment::Goto(label));
+        statements.extend(vec![
             format!("@{}${}", self.get_label_prefix(), label),
             String::from("0;JMP"),
-        ];
+        ]);
         self.write_statements(statements);
     }
 
     pub fn write_if(&mut self, label: &str) {
-        let mut statements = vec![format!("// if {}", label)];
+        let mut statements = self.get_comment(AsmComment::If(label));
         statements.extend(self.first_pop.clone());
         statements.push(format!("@{}${}", self.get_label_prefix(), label));
         statements.push(String::from("D;JNE"));
@@ -305,20 +554,24 @@ impl CodeWriter {
 
     pub fn write_call(&mut self, function_name: &str, nvars: i32) {
         let return_label = format!("{}$ret.{}", self.get_label_prefix(), self.return_index);
-        let mut statements = vec![
-            format!("// call {} {}", function_name, nvars),
+        self.add_symbol(&return_label, SymbolKind::Return);
+        let mut statements = self.get_comment(AsmComment::Call { name: function_name, nargs: nvars });
+        statements.extend(vec![
             format!("@{}", &return_label),
             String::from("D=A"),
-        ];
+        ]);
         statements.extend(self.final_push.clone());
-        statements.extend(self.get_push_segment_asm("LCL"));
-        statements.extend(self.get_push_segment_asm("ARG"));
-        statements.extend(self.get_push_segment_asm("THIS"));
-        statements.extend(self.get_push_segment_asm("THAT"));
+        let frame_segments = self.get_frame_segments(function_name);
+        for segment in &frame_segments {
+            statements.extend(self.get_push_segment_asm(segment));
+        }
+        for segment in &["LCL", "ARG", "THIS", "THAT"][frame_segments.len()..] {
+            self.saved_instruction_count += self.get_push_segment_asm(segment).len();
+        }
         statements.extend(vec![
             String::from("@SP"),
             String::from("D=M"),
-            String::from("@5"),
+            format!("@{}", frame_segments.len() + 1),
             String::from("D=D-A"),
             format!("@{}", nvars),
             String::from("D=D-A"),
@@ -336,20 +589,78 @@ impl CodeWriter {
         self.return_index += 1;
     }
 
+    pub fn write_tail_call(&mut self, function_name: &str, nvars: i32) {
+        let caller_segments = self.get_frame_segments(&self.current_function_name);
+        let callee_segments = self.get_frame_segments(function_name);
+        let rom_start = self.rom_address;
+        if callee_segments.len() < caller_segments.len() {
+            self.write_call(function_name, nvars);
+            self.add_command_stat("call", rom_start);
+            let rom_start = self.rom_address;
+            self.write_return();
+            self.add_command_stat("return", rom_start);
+            return;
+        }
+        // a compact frame function only calls compact frame functions
+        assert_eq!(callee_segments.len(), caller_segments.len());
+
+        let mut statements = self.get_comment(AsmComment::TailCall { name: function_name, nargs: nvars });
+        let frame_size = caller_segments.len() as i32 + 1;
+        statements.extend(vec![
+            String::from("@LCL"),
+            String::from("D=M"),
+            format!("@{}", frame_size),
+            String::from("D=D-A"),
+            String::from("@R13"),
+            String::from("M=D"),
+            String::from("@SP"),
+            String::from("D=M"),
+            String::from("@R14"),
+            String::from("M=D"),
+        ]);
+        statements.extend(self.get_copy_asm(frame_size));
+        statements.extend(vec![
+            String::from("@R14"),
+            String::from("D=M"),
+            format!("@{}", nvars + frame_size),
+            String::from("D=D-A"),
+            String::from("@R13"),
+            String::from("M=D"),
+            String::from("@ARG"),
+            String::from("D=M"),
+            String::from("@R14"),
+            String::from("M=D"),
+        ]);
+        statements.extend(self.get_copy_asm(nvars + frame_size));
+        statements.extend(vec![
+            String::from("@R14"),
+            String::from("D=M"),
+            String::from("@SP"),
+            String::from("M=D"),
+            String::from("@LCL"),
+            String::from("M=D"),
+            format!("@{}", function_name),
+            String::from("0;JMP"),
+        ]);
+        self.write_statements(statements);
+        self.add_command_stat("tail call", rom_start);
+    }
+
     pub fn write_return(&mut self) {
-        let mut statements = vec![
-            String::from("// return"),
+        let frame_segments = self.get_frame_segments(&self.current_function_name);
+        let mut statements = self.get_comment(AsmComment::Return);
+        statements.extend(vec![
             String::from("@LCL"),
             String::from("D=M"),
             String::from("@R13"),
             String::from("M=D"),
-            String::from("@5"),
+            format!("@{}", frame_segments.len() + 1),
             String::from("D=D-A"),
             String::from("A=D"),
             String::from("D=M"),
             String::from("@R14"),
             String::from("M=D"),
-        ];
+        ]);
         statements.extend(self.first_pop.clone());
         statements.extend(vec![
             String::from("@ARG"),
@@ -361,10 +672,12 @@ impl CodeWriter {
             String::from("@SP"),
             String::from("M=D"),
         ]);
-        statements.extend(self.get_recover_segment_asm("THAT", 1));
-        statements.extend(self.get_recover_segment_asm("THIS", 2));
-        statements.extend(self.get_recover_segment_asm("ARG", 3));
-        statements.extend(self.get_recover_segment_asm("LCL", 4));
+        for (offset, segment) in frame_segments.iter().rev().enumerate() {
+            statements.extend(self.get_recover_segment_asm(segment, offset as u32 + 1));
+        }
+        for segment in &["LCL", "ARG", "THIS", "THAT"][frame_segments.len()..] {
+            self.saved_instruction_count += self.get_recover_segment_asm(segment, 0).len();
+        }
         statements.extend(vec![
             String::from("@R14"),
             String::from("A=M"),
@@ -375,15 +688,52 @@ impl CodeWriter {
     }
 
     pub fn write_bootstrap(&mut self) {
-        let statements = vec![
-            String::from("// bootstrap"),
+        let mut statements = self.get_comment(AsmComment::Bootstrap);
+        statements.extend(vec![
             String::from("@256"),
             String::from("D=A"),
             String::from("@SP"),
             String::from("M=D"),
-        ];
+        ]);
+        self.is_writing_bootstrap = true;
         self.write_statements(statements);
         self.write_call("Sys.init", 0);
+        self.is_writing_bootstrap = false;
+    }
+
+    fn get_copy_asm(&mut self, count: i32) -> Vec<String> {
+        let loop_label = format!("{}_COPY{}", self.get_label_prefix(), self.branch_index);
+        self.add_symbol(&loop_label, SymbolKind::Branch);
+        self.branch_index += 1;
+        vec![
+            format!("@{}", count),
+            String::from("D=A"),
+            String::from("@R15"),
+            String::from("M=D"),
+            format!("({})", loop_label),
+            String::from("@R13"),
+            String::from("A=M"),
+            String::from("D=M"),
+            String::from("@R14"),
+            String::from("A=M"),
+            String::from("M=D"),
+            String::from("@R13"),
+            String::from("M=M+1"),
+            String::from("@R14"),
+            String::from("M=M+1"),
+            String::from("@R15"),
+            String::from("MD=M-1"),
+            format!("@{}", loop_label),
+            String::from("D;JGT"),
+        ]
+    }
+
+    fn get_frame_segments(&self, function_name: &str) -> Vec<&'static str> {
+        if self.compact_frame_functions.contains(function_name) {
+            vec!["LCL", "ARG"]
+        } else {
+            vec!["LCL", "ARG", "THIS", "THAT"]
+        }
     }
 
     fn get_recover_segment_asm(&self, segment: &str, index: u32) -> Vec<String> {
@@ -411,10 +761,12 @@ impl CodeWriter {
 
 #[cfg(test)]
 mod tests {
-    use std::{fs, path::Path};
-    use crate::util::load_text;
+    use std::{collections::HashSet, fs, path::Path};
+    use crate::{
+        asm_comment::CommentLevel, parser::{Command, Parser}, source_map::SourceMapping, util::load_text,
+    };
 
-    use super::CodeWriter;
+    use super::{AsmSymbol, CodeWriter, SymbolKind};
 
     #[test]
     fn test_write_arithmetic_given_add() {
@@ -512,6 +864,26 @@ mod tests {
         test_write_push_pop("popthat2", vec![("pop", "that", 2)]);
     }
 
+    #[test]
+    fn test_write_push_pop_given_push_local_index_0() {
+        test_write_push_pop("pushlocal0", vec![("push", "local", 0)]);
+    }
+
+    #[test]
+    fn test_write_push_pop_given_push_local_large_index() {
+        test_write_push_pop("pushlocal3", vec![("push", "local", 3)]);
+    }
+
+    #[test]
+    fn test_write_push_pop_given_pop_local_index_0() {
+        test_write_push_pop("poplocal0", vec![("pop", "local", 0)]);
+    }
+
+    #[test]
+    fn test_write_push_pop_given_pop_local_large_index() {
+        test_write_push_pop("poplocal7", vec![("pop", "local", 7)]);
+    }
+
     #[test]
     fn test_write_push_pop_given_push_pointer() {
         test_write_push_pop("pushpointer", vec![
@@ -668,6 +1040,203 @@ mod tests {
         fs::remove_file(out_file).unwrap();
     }
 
+    #[test]
+    fn test_get_source_mappings() {
+        let out_file = "SourceMap.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.write_bootstrap();
+        code_writer.set_file_name("Main");
+        code_writer.write_command(&Command::new("function Main.main 0").with_line_number(2));
+        code_writer.write_command(&Command::new("push constant 7").with_line_number(3));
+        code_writer.write_command(&Command::new("label END").with_line_number(4));
+        let mapping = |line_number, asm_lines: (usize, usize), rom: (usize, usize)| SourceMapping {
+            vm_file: String::from("Main.vm"),
+            line_number,
+            function_name: String::from("Main.main"),
+            asm_start_line: asm_lines.0,
+            asm_end_line: asm_lines.1,
+            rom_start: rom.0,
+            rom_end: rom.1,
+        };
+
+        assert_eq!(code_writer.get_source_mappings(), &[
+            mapping(2, (58, 59), (53, 53)),
+            mapping(3, (60, 67), (53, 60)),
+            mapping(4, (68, 69), (60, 60)),
+        ]);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_write_vm_comments() {
+        let out_file = "VmComments.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+        let mut parser = Parser::new("// Main.run\nfunction Main.run 0\n// let x = 1\npush constant 1 // x\nreturn");
+
+        code_writer.set_file_name("Main");
+        code_writer.set_vm_comments(true, true);
+        while parser.has_more_lines() {
+            parser.advance().unwrap();
+            code_writer.write_command(parser.command());
+        }
+        let source_mappings = code_writer.get_source_mappings().to_vec();
+        drop(code_writer);
+
+        let asm_text = fs::read_to_string(out_file).unwrap();
+        let comments: Vec<&str> = asm_text.lines().filter(|line| line.starts_with("//")).collect();
+        assert_eq!(comments, [
+            "// > Main.asm",
+            "// Main.vm:1: Main.run",
+            "// function Main.run 0",
+            "// Main.vm:3: let x = 1",
+            "// Main.vm:4: x",
+            "// push constant 1",
+            "// return",
+        ]);
+        let lines: Vec<(usize, &str)> = source_mappings.iter()
+            .map(|mapping| (mapping.line_number, mapping.function_name.as_str()))
+            .collect();
+        assert_eq!(lines, [(2, "Main.run"), (4, "Main.run"), (5, "Main.run")]);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_write_command_given_no_comments() {
+        let vm_text = "// Main.run\nfunction Main.run 0\npush constant 1\npush constant 2\nlt\ncall Main.run 0\nreturn";
+        let out_file = "NoComments.asm";
+        let write_asm = |comment_level: CommentLevel| {
+            let mut code_writer = CodeWriter::new(out_file);
+            let mut parser = Parser::new(vm_text);
+            code_writer.set_comment_level(comment_level);
+            code_writer.set_vm_comments(true, false);
+            code_writer.write_bootstrap();
+            code_writer.set_file_name("Main");
+            while parser.has_more_lines() {
+                parser.advance().unwrap();
+                code_writer.write_command(parser.command());
+            }
+            drop(code_writer);
+            let asm_text = fs::read_to_string(out_file).unwrap();
+            fs::remove_file(out_file).unwrap();
+            asm_text
+        };
+
+        let asm_text = write_asm(CommentLevel::None);
+        let commented_asm_text = write_asm(CommentLevel::Commands);
+
+        assert!(commented_asm_text.contains("// > Main.asm\n"));
+        assert!(commented_asm_text.contains("// Main.run\n"));
+        assert!(commented_asm_text.contains("// function Main.run 0\n"));
+        assert!(!asm_text.contains("//"));
+        let uncommented_lines: Vec<&str> = commented_asm_text.lines()
+            .filter(|line| !line.starts_with("//"))
+            .collect();
+        assert_eq!(asm_text.lines().collect::<Vec<&str>>(), uncommented_lines);
+    }
+
+    #[test]
+    fn test_labels_are_numbered_per_function() {
+        let out_file = "FunctionLabels.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.set_file_name("Main");
+        for function_name in ["Main.a", "Main.b"] {
+            code_writer.write_function(function_name, 0);
+            code_writer.write_arithmetic("lt");
+            code_writer.write_call("Math.max", 2);
+            code_writer.write_arithmetic("eq");
+            code_writer.write_call("Math.min", 2);
+        }
+        let names: Vec<&str> = code_writer.get_symbols().iter()
+            .filter(|symbol| symbol.kind != SymbolKind::Function)
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+
+        assert_eq!(names, [
+            "Main.a_THEN1", "Main.a_END1", "Main.a$ret.1", "Main.a_THEN2", "Main.a_END2", "Main.a$ret.2",
+            "Main.b_THEN1", "Main.b_END1", "Main.b$ret.1", "Main.b_THEN2", "Main.b_END2", "Main.b$ret.2",
+        ]);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_get_symbols() {
+        let out_file = "Symbols.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.write_bootstrap();
+        code_writer.set_file_name("Main");
+        code_writer.write_function("Main.main", 0);
+        code_writer.write_push_pop("pop", "static", 1);
+        code_writer.write_push_pop("push", "static", 1);
+        code_writer.write_label("LOOP");
+        code_writer.write_arithmetic("eq");
+        code_writer.write_call("Math.add", 2);
+        let symbol = |name: &str, kind, file_name: Option<&str>| AsmSymbol {
+            name: name.to_string(),
+            kind,
+            file_name: file_name.map(str::to_string),
+        };
+
+        assert_eq!(code_writer.get_symbols(), &[
+            symbol("Symbols$ret.1", SymbolKind::Return, None),
+            symbol("Main.main", SymbolKind::Function, Some("Main")),
+            symbol("Main.1", SymbolKind::Static, Some("Main")),
+            symbol("Main.main$LOOP", SymbolKind::Label, Some("Main")),
+            symbol("Main.main_THEN1", SymbolKind::Branch, Some("Main")),
+            symbol("Main.main_END1", SymbolKind::Branch, Some("Main")),
+            symbol("Main.main$ret.1", SymbolKind::Return, Some("Main")),
+        ]);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_write_tail_call() {
+        let out_file = "TailCall.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.write_function("TailCall.test", 0);
+        code_writer.write_tail_call("Math.add", 2);
+
+        verify_output(out_file);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_write_tail_call_given_compact_callee() {
+        let out_file = "CompactTailCall.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.set_compact_frame_functions(HashSet::from([String::from("Math.add")]));
+        code_writer.write_function("CompactTailCall.test", 0);
+        code_writer.write_tail_call("Math.add", 2);
+        let stats: Vec<(&str, usize)> = code_writer.get_command_stats().iter()
+            .map(|command_stat| (command_stat.kind, command_stat.instruction_count))
+            .collect();
+
+        assert_eq!(stats, [("call", 35), ("return", 57)]);
+        fs::remove_file(out_file).unwrap();
+    }
+
+    #[test]
+    fn test_write_call_given_compact_frame_functions() {
+        let out_file = "CompactCall.asm";
+        let mut code_writer = CodeWriter::new(out_file);
+
+        code_writer.set_compact_frame_functions(HashSet::from([
+            String::from("CompactCall.test"),
+            String::from("Math.add"),
+        ]));
+        code_writer.write_function("CompactCall.test", 0);
+        code_writer.write_call("Math.add", 2);
+        code_writer.write_return();
+
+        verify_output(out_file);
+        assert_eq!(code_writer.get_saved_instruction_count(), 30);
+        fs::remove_file(out_file).unwrap();
+    }
+
     #[test]
     fn test_write_return() {
         let out_file = "Return.asm";
@@ -0,0 +1,117 @@
+use crate::json::Json;
+
+use super::{registry::{Level, LINTS}, Diagnostic, Severity};
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    Json,
+    Sarif,
+}
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub fn to_json(diagnostic: &Diagnostic) -> Json {
+    Json::object(vec![
+        ("file", Json::from(diagnostic.path.as_str())),
+        ("line", Json::from(diagnostic.line_number)),
+        ("column", Json::from(diagnostic.column)),
+        ("severity", Json::from(diagnostic.severity.to_string().as_str())),
+        ("code", Json::from(diagnostic.lint.name)),
+        ("message", Json::from(diagnostic.message.as_str())),
+    ])
+}
+
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> Json {
+    let rules = LINTS.iter()
+        .map(|lint| Json::object(vec![
+            ("id", Json::from(lint.name)),
+            ("shortDescription", Json::object(vec![("text", Json::from(lint.summary))])),
+            ("fullDescription", Json::object(vec![("text", Json::from(lint.explanation))])),
+            ("defaultConfiguration", Json::object(vec![
+                ("level", Json::from(get_sarif_level(lint.default_level))),
+            ])),
+        ]))
+        .collect();
+
+    let results = diagnostics.iter()
+        .map(|diagnostic| {
+            let rule_index = LINTS.iter().position(|lint| lint.name == diagnostic.lint.name).unwrap();
+            let level = match diagnostic.severity {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            Json::object(vec![
+                ("ruleId", Json::from(diagnostic.lint.name)),
+                ("ruleIndex", Json::from(rule_index)),
+                ("level", Json::from(level)),
+                ("message", Json::object(vec![("text", Json::from(diagnostic.message.as_str()))])),
+                ("locations", Json::Array(vec![Json::object(vec![
+                    ("physicalLocation", Json::object(vec![
+                        ("artifactLocation", Json::object(vec![
+                            ("uri", Json::from(diagnostic.path.replace('\\', "/").as_str())),
+                        ])),
+                        ("region", Json::object(vec![
+                            ("startLine", Json::from(diagnostic.line_number)),
+                            ("startColumn", Json::from(diagnostic.column)),
+                        ])),
+                    ])),
+                ])])),
+            ])
+        })
+        .collect();
+
+    Json::object(vec![
+        ("$schema", Json::from(SARIF_SCHEMA)),
+        ("version", Json::from("2.1.0")),
+        ("runs", Json::Array(vec![Json::object(vec![
+            ("tool", Json::object(vec![
+                ("driver", Json::object(vec![
+                    ("name", Json::from(env!("CARGO_PKG_NAME"))),
+                    ("version", Json::from(env!("CARGO_PKG_VERSION"))),
+                    ("rules", Json::Array(rules)),
+                ])),
+            ])),
+            ("results", Json::Array(results)),
+        ])])),
+    ])
+}
+
+fn get_sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Allow => "none",
+        Level::Warn => "warning",
+        Level::Deny | Level::Forbid => "error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::{registry::UNUSED_LABEL, Diagnostic};
+
+    use super::{to_json, to_sarif};
+
+    #[test]
+    fn test_to_json() {
+        let diagnostic = Diagnostic::new(&UNUSED_LABEL, "Main.vm", 2, String::from("label 'LOOP' is never used"));
+
+        assert_eq!(to_json(&diagnostic).to_string(), concat!(
+            r#"{"file":"Main.vm","line":2,"column":1,"severity":"warning","code":"unused_label","#,
+            r#""message":"label 'LOOP' is never used"}"#));
+    }
+
+    #[test]
+    fn test_to_sarif() {
+        let diagnostic = Diagnostic::new(&UNUSED_LABEL, "Main.vm", 2, String::from("label 'LOOP' is never used"))
+            .with_column(3);
+        let sarif = to_sarif(&[diagnostic]).to_string();
+
+        assert!(sarif.starts_with(r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","#));
+        assert!(sarif.contains(concat!(
+            r#""results":[{"ruleId":"unused_label","ruleIndex":14,"level":"warning","#,
+            r#""message":{"text":"label 'LOOP' is never used"},"locations":[{"physicalLocation":"#,
+            r#"{"artifactLocation":{"uri":"Main.vm"},"region":{"startLine":2,"startColumn":3}}}]}]"#)));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{collections::HashMap, fmt};
+
+use registry::{find_lint, Level, Lint};
+
+pub mod format;
+pub mod registry;
+pub mod render;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub lint: &'static Lint,
+    pub path: String,
+    pub line_number: usize,
+    pub column: usize,
+    pub message: String,
+    pub snippet: Option<Snippet>,
+    pub help: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snippet {
+    pub source_line: String,
+    pub length: usize,
+}
+
+impl Diagnostic {
+    pub fn new(lint: &'static Lint, path: &str, line_number: usize, message: String) -> Diagnostic {
+        let severity = match lint.default_level {
+            Level::Allow | Level::Warn => Severity::Warning,
+            Level::Deny | Level::Forbid => Severity::Error,
+        };
+        Diagnostic {
+            severity,
+            lint,
+            path: path.to_string(),
+            line_number,
+            column: 1,
+            message,
+            snippet: None,
+            help: None,
+        }
+    }
+
+    pub fn with_column(mut self, column: usize) -> Diagnostic {
+        self.column = column;
+        self
+    }
+
+    pub fn with_snippet(mut self, source_line: &str, length: usize) -> Diagnostic {
+        self.snippet = Some(Snippet { source_line: source_line.to_string(), length });
+        self
+    }
+
+    pub fn with_help(mut self, help: String) -> Diagnostic {
+        self.help = Some(help);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct LintLevels {
+    levels: HashMap<&'static str, Level>,
+    deny_warnings: bool,
+}
+
+impl LintLevels {
+    pub fn set_level(&mut self, name: &str, level: Level) -> Result<(), String> {
+        let lint = find_lint(name).ok_or_else(|| format!("Unknown lint '{}'", name))?;
+        if lint.default_level == Level::Forbid && level != Level::Deny {
+            return Err(format!("Cannot change the level of '{}', which is always an error", name));
+        }
+        self.levels.insert(lint.name, level);
+        Ok(())
+    }
+
+    pub fn set_deny_warnings(&mut self, deny_warnings: bool) {
+        self.deny_warnings = deny_warnings;
+    }
+
+    pub fn get_level(&self, lint: &Lint) -> Level {
+        let level = self.levels.get(lint.name).copied().unwrap_or(lint.default_level);
+        if level == Level::Warn && self.deny_warnings { Level::Deny } else { level }
+    }
+
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics.into_iter()
+            .filter_map(|diagnostic| {
+                let severity = match self.get_level(diagnostic.lint) {
+                    Level::Allow => return None,
+                    Level::Warn => Severity::Warning,
+                    Level::Deny | Level::Forbid => Severity::Error,
+                };
+                Some(Diagnostic { severity, ..diagnostic })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]: {}:{}: {}",
+            self.severity, self.lint.name, self.path, self.line_number, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        registry::{Level, UNDEFINED_FUNCTION, UNDEFINED_LABEL, UNUSED_FUNCTION, UNUSED_LABEL},
+        Diagnostic, LintLevels, Severity,
+    };
+
+    fn new_diagnostics() -> Vec<Diagnostic> {
+        vec![
+            Diagnostic::new(&UNUSED_LABEL, "Main.vm", 2, String::from("label 'LOOP' is never used")),
+            Diagnostic::new(&UNUSED_FUNCTION, "Main.vm", 5, String::from("function 'Main.f' is never called")),
+            Diagnostic::new(&UNDEFINED_FUNCTION, "Main.vm", 7, String::from("call to undefined function 'Math.add'")),
+        ]
+    }
+
+    #[test]
+    fn test_display() {
+        let diagnostic = Diagnostic::new(&UNDEFINED_LABEL, "Main.vm", 3, String::from("undefined label 'LOOP'"));
+        assert_eq!(diagnostic.to_string(), "error[undefined_label]: Main.vm:3: undefined label 'LOOP'");
+
+        let diagnostic = Diagnostic::new(&UNUSED_LABEL, "Main.vm", 2, String::from("label 'LOOP' is never used"));
+        assert_eq!(diagnostic.to_string(), "warning[unused_label]: Main.vm:2: label 'LOOP' is never used");
+    }
+
+    #[test]
+    fn test_apply_lint_levels() {
+        let mut lint_levels = LintLevels::default();
+        lint_levels.set_level("unused_label", Level::Allow).unwrap();
+        lint_levels.set_level("unused_function", Level::Deny).unwrap();
+        let diagnostics = lint_levels.apply(new_diagnostics());
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].lint.name, "unused_function");
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_apply_lint_levels_given_deny_warnings() {
+        let mut lint_levels = LintLevels::default();
+        lint_levels.set_deny_warnings(true);
+        lint_levels.set_level("undefined_function", Level::Warn).unwrap();
+        lint_levels.set_level("unused_label", Level::Allow).unwrap();
+        let severities: Vec<Severity> = lint_levels.apply(new_diagnostics()).iter()
+            .map(|diagnostic| diagnostic.severity)
+            .collect();
+
+        assert_eq!(severities, vec![Severity::Error, Severity::Error]);
+    }
+
+    #[test]
+    fn test_set_level_given_invalid_lint() {
+        let mut lint_levels = LintLevels::default();
+
+        assert_eq!(lint_levels.set_level("unused_variable", Level::Allow),
+            Err(String::from("Unknown lint 'unused_variable'")));
+        assert_eq!(lint_levels.set_level("undefined_label", Level::Allow),
+            Err(String::from("Cannot change the level of 'undefined_label', which is always an error")));
+        assert!(lint_levels.set_level("undefined_label", Level::Deny).is_ok());
+    }
+}
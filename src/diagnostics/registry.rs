@@ -0,0 +1,211 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Lint {
+    pub name: &'static str,
+    pub default_level: Level,
+    pub summary: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const INVALID_COMMAND: Lint = Lint {
+    name: "invalid_command",
+    default_level: Level::Forbid,
+    summary: "a line is not a valid VM command",
+    explanation: "\
+The line does not parse as a VM command: the command or segment name is unknown,
+the number of arguments is wrong, or an index is not a non-negative integer.
+Nothing is translated while a file contains invalid commands.",
+};
+
+pub const DUPLICATE_FUNCTION: Lint = Lint {
+    name: "duplicate_function",
+    default_level: Level::Forbid,
+    summary: "a function is defined more than once",
+    explanation: "\
+Two `function` commands declare the same name, in the same file or in different
+files of a folder. The translator emits a label for each function, so the second
+definition would produce a duplicate label in the assembly and calls could not
+tell the two apart. Rename or remove one of the definitions.",
+};
+
+pub const DUPLICATE_LABEL: Lint = Lint {
+    name: "duplicate_label",
+    default_level: Level::Forbid,
+    summary: "a label is declared twice in the same function",
+    explanation: "\
+Labels are scoped to the function that declares them, and the same name may not
+be declared twice within one function. `goto` and `if-goto` would be ambiguous,
+so this is always an error.",
+};
+
+pub const UNDEFINED_LABEL: Lint = Lint {
+    name: "undefined_label",
+    default_level: Level::Forbid,
+    summary: "goto or if-goto targets a label the function does not declare",
+    explanation: "\
+A `goto` or `if-goto` names a label that is not declared in the same function.
+The Hack assembler would silently treat the name as a new variable and jump to
+its RAM address, so this is always an error.",
+};
+
+pub const UNDEFINED_FUNCTION: Lint = Lint {
+    name: "undefined_function",
+    default_level: Level::Warn,
+    summary: "a call targets a function no file defines",
+    explanation: "\
+A `call` names a function that none of the translated files defines. This is
+only checked when translating a folder, since a single file may legitimately
+call functions defined elsewhere. Allow this lint when the missing functions
+are provided by another part of the build, such as the Jack OS.",
+};
+
+pub const STACK_UNDERFLOW: Lint = Lint {
+    name: "stack_underflow",
+    default_level: Level::Warn,
+    summary: "a command pops more values than the function pushed",
+    explanation: "\
+On some path through the function, a command needs more values than the
+function has pushed onto its working stack. The command would read and
+overwrite the saved frame or the caller's values.
+
+    function Main.main 0
+    push constant 1
+    add                 // needs 2 values, only 1 is on the stack",
+};
+
+pub const UNBALANCED_RETURN: Lint = Lint {
+    name: "unbalanced_return",
+    default_level: Level::Warn,
+    summary: "the stack does not hold exactly one value at return",
+    explanation: "\
+Every function returns the single value on top of its working stack. Reaching
+`return` with no value, or with values left over, usually means a push or pop is
+missing on that path.",
+};
+
+pub const STACK_MISMATCH: Lint = Lint {
+    name: "stack_mismatch",
+    default_level: Level::Warn,
+    summary: "paths reaching a label leave different stack depths",
+    explanation: "\
+Two paths of control flow reach the same label with a different number of
+values on the working stack, for example when one branch of an `if-goto` pushes
+a value the other does not. Code after the label cannot know where its operands
+are.",
+};
+
+pub const UNBOUNDED_RECURSION: Lint = Lint {
+    name: "unbounded_recursion",
+    default_level: Level::Warn,
+    summary: "recursion makes the worst-case stack usage unbounded",
+    explanation: "\
+A cycle of calls reachable from `Sys.init` means the stack can grow without a
+bound that the translator can compute, so it cannot prove the program fits
+between RAM 256 and 2047. Allow this lint for intentionally recursive programs.",
+};
+
+pub const STACK_OVERFLOW: Lint = Lint {
+    name: "stack_overflow",
+    default_level: Level::Warn,
+    summary: "the worst-case stack usage exceeds the stack region",
+    explanation: "\
+Following the deepest chain of calls from `Sys.init`, the frames, locals and
+working stacks need more words than the 1792 available between RAM 256 and
+2047. The stack would run into the heap at RAM 2048.",
+};
+
+pub const MISSING_ARGUMENT: Lint = Lint {
+    name: "missing_argument",
+    default_level: Level::Warn,
+    summary: "a call passes fewer arguments than the callee uses",
+    explanation: "\
+The callee accesses an `argument` index that the call does not provide. The
+access would read the caller's working stack or saved frame instead.",
+};
+
+pub const INCONSISTENT_ARGUMENTS: Lint = Lint {
+    name: "inconsistent_arguments",
+    default_level: Level::Warn,
+    summary: "calls to the same function pass different argument counts",
+    explanation: "\
+A function is called with a different number of arguments than at its first
+call site. VM functions have a fixed number of parameters, so one of the calls
+is probably wrong.",
+};
+
+pub const LOCAL_OUT_OF_RANGE: Lint = Lint {
+    name: "local_out_of_range",
+    default_level: Level::Warn,
+    summary: "a local index exceeds the count declared by function",
+    explanation: "\
+A `push local` or `pop local` uses an index that is not below the number of
+locals in the `function` command. The access would touch the working stack, which
+`call` and arithmetic commands overwrite.",
+};
+
+pub const UNREACHABLE_CODE: Lint = Lint {
+    name: "unreachable_code",
+    default_level: Level::Warn,
+    summary: "commands follow goto or return without a label",
+    explanation: "\
+Commands after an unconditional `goto` or a `return` can only run if a label
+lets control flow reach them. Without one they are dead and are still emitted
+into the ROM.",
+};
+
+pub const UNUSED_LABEL: Lint = Lint {
+    name: "unused_label",
+    default_level: Level::Warn,
+    summary: "a label is never targeted",
+    explanation: "\
+No `goto` or `if-goto` in the function targets this label. It may be left over
+from an edit, or a jump may be misspelled.",
+};
+
+pub const UNUSED_STATIC: Lint = Lint {
+    name: "unused_static",
+    default_level: Level::Warn,
+    summary: "a static is written but never read",
+    explanation: "\
+A file pops into a `static` index that it never pushes. Statics are private to
+their file, so the value can never be observed.",
+};
+
+pub const UNINITIALIZED_STATIC: Lint = Lint {
+    name: "uninitialized_static",
+    default_level: Level::Warn,
+    summary: "a static is read but never written",
+    explanation: "\
+A file pushes a `static` index that it never pops into. The value is whatever
+RAM holds at that address, which is 0 only on a freshly reset machine.",
+};
+
+pub const UNUSED_FUNCTION: Lint = Lint {
+    name: "unused_function",
+    default_level: Level::Warn,
+    summary: "a function is never called",
+    explanation: "\
+No `call` in the translated folder targets this function, and it is not
+`Sys.init`. It still occupies ROM unless dead code elimination removes it.
+Functions called only from outside, such as event handlers, can be kept with
+`--keep` and this lint allowed.",
+};
+
+pub const LINTS: [&Lint; 18] = [
+    &INVALID_COMMAND, &DUPLICATE_FUNCTION, &DUPLICATE_LABEL, &UNDEFINED_LABEL, &UNDEFINED_FUNCTION,
+    &STACK_UNDERFLOW, &UNBALANCED_RETURN, &STACK_MISMATCH,
+    &UNBOUNDED_RECURSION, &STACK_OVERFLOW,
+    &MISSING_ARGUMENT, &INCONSISTENT_ARGUMENTS, &LOCAL_OUT_OF_RANGE,
+    &UNREACHABLE_CODE, &UNUSED_LABEL, &UNUSED_STATIC, &UNINITIALIZED_STATIC, &UNUSED_FUNCTION,
+];
+
+pub fn find_lint(name: &str) -> Option<&'static Lint> {
+    LINTS.iter().copied().find(|lint| lint.name == name)
+}
@@ -0,0 +1,77 @@
+use super::{Diagnostic, Severity};
+
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+pub fn render(diagnostic: &Diagnostic, use_color: bool) -> String {
+    let paint = |color: &str, text: &str| {
+        if use_color { format!("{}{}{}", color, text, RESET) } else { text.to_string() }
+    };
+    let severity_color = match diagnostic.severity {
+        Severity::Warning => YELLOW,
+        Severity::Error => RED,
+    };
+
+    let Some(snippet) = &diagnostic.snippet else {
+        return format!("{}: {}:{}: {}",
+            paint(severity_color, &format!("{}[{}]", diagnostic.severity, diagnostic.lint.name)),
+            diagnostic.path, diagnostic.line_number, diagnostic.message);
+    };
+
+    let gutter = " ".repeat(diagnostic.line_number.to_string().len());
+    let indent: String = snippet.source_line.chars()
+        .take(diagnostic.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    let mut lines = vec![
+        format!("{}: {}",
+            paint(severity_color, &format!("{}[{}]", diagnostic.severity, diagnostic.lint.name)),
+            paint(BOLD, &format!("{}:{}:{}: {}",
+                diagnostic.path, diagnostic.line_number, diagnostic.column, diagnostic.message))),
+        format!("{} {}", gutter, paint(BLUE, "|")),
+        format!("{} {} {}", paint(BLUE, &diagnostic.line_number.to_string()), paint(BLUE, "|"),
+            snippet.source_line.trim_end()),
+        format!("{} {} {}{}", gutter, paint(BLUE, "|"), indent,
+            paint(severity_color, &"^".repeat(snippet.length))),
+    ];
+    if let Some(help) = &diagnostic.help {
+        lines.push(format!("{} {} help: {}", gutter, paint(BLUE, "="), help));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::{registry::{INVALID_COMMAND, UNUSED_LABEL}, Diagnostic};
+
+    use super::render;
+
+    #[test]
+    fn test_render() {
+        let diagnostic = Diagnostic::new(&INVALID_COMMAND, "Main.vm", 12, String::from("unknown segment 'locl'"))
+            .with_column(7)
+            .with_snippet("\tpush locl 0 // x", 4)
+            .with_help(String::from("did you mean `local`?"));
+
+        assert_eq!(render(&diagnostic, false), "\
+error[invalid_command]: Main.vm:12:7: unknown segment 'locl'
+   |
+12 | \tpush locl 0 // x
+   | \t     ^^^^
+   = help: did you mean `local`?");
+    }
+
+    #[test]
+    fn test_render_given_no_snippet() {
+        let diagnostic = Diagnostic::new(&UNUSED_LABEL, "Main.vm", 2, String::from("label 'LOOP' is never used"));
+
+        assert_eq!(render(&diagnostic, false), diagnostic.to_string());
+        assert_eq!(render(&diagnostic, true),
+            "\x1b[1;33mwarning[unused_label]\x1b[0m: Main.vm:2: label 'LOOP' is never used");
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{parser::CommandType, program::VmFile};
+
+
+const BODY_INDENT: &str = "    ";
+const LABEL_INDENT: &str = "  ";
+
+pub fn format_vm(vm_file: &VmFile) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut last_line_number = 0;
+    let mut in_function = false;
+    let mut push_line = |line_number: usize, text: String| {
+        if line_number > last_line_number + 1 && !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(text);
+        last_line_number = line_number;
+    };
+
+    for command in &vm_file.commands {
+        let indent = match command.get_command_type() {
+            CommandType::Function => {
+                in_function = true;
+                ""
+            },
+            CommandType::Label if in_function => LABEL_INDENT,
+            _ if in_function => BODY_INDENT,
+            _ => "",
+        };
+        let mut trailing_comment = None;
+        for (line_number, text) in command.get_comments() {
+            if *line_number == command.get_line_number() {
+                trailing_comment = Some(text);
+            } else {
+                push_line(*line_number, format!("{}{}", indent, format_comment(text)));
+            }
+        }
+        let formatted = match trailing_comment {
+            Some(text) => format!("{}{} {}", indent, command, format_comment(text)),
+            None => format!("{}{}", indent, command),
+        };
+        push_line(command.get_line_number(), formatted);
+    }
+
+    let indent = if in_function { BODY_INDENT } else { "" };
+    for (line_number, text) in &vm_file.remaining_comments {
+        push_line(*line_number, format!("{}{}", indent, format_comment(text)));
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+fn format_comment(text: &str) -> String {
+    format!("//{}", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::VmFile;
+
+    use super::format_vm;
+
+    fn format(text: &str) -> String {
+        format_vm(&VmFile::new("Main", text))
+    }
+
+    #[test]
+    fn test_format_vm() {
+        let text = "\
+// Multiplies two numbers
+//
+
+
+function  Math.multiply 1
+push constant 0
+  pop local 0   // sum = 0
+label LOOP
+\t// loop while y > 0
+        push argument 1
+    if-goto END
+    goto LOOP
+label END
+push local 0
+return
+
+  // end
+";
+        assert_eq!(format(text), "\
+// Multiplies two numbers
+//
+
+function Math.multiply 1
+    push constant 0
+    pop local 0 // sum = 0
+  label LOOP
+    // loop while y > 0
+    push argument 1
+    if-goto END
+    goto LOOP
+  label END
+    push local 0
+    return
+
+    // end
+");
+    }
+
+    #[test]
+    fn test_format_vm_keeps_comment_text() {
+        let text = "//x\n//   indented\n//  +---+\n//  | A |\nfunction Main.main 0\n    push constant 1 //trailing\n    return\n";
+
+        assert_eq!(format(text), text);
+    }
+
+    #[test]
+    fn test_format_vm_is_idempotent() {
+        let text = "label START\npush constant 1\nfunction Main.main 0\n  call Main.run 0\n  return\n";
+        let formatted = format(text);
+
+        assert_eq!(formatted, "label START\npush constant 1\nfunction Main.main 0\n    call Main.run 0\n    return\n");
+        assert_eq!(format(&formatted), formatted);
+    }
+}
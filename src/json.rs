@@ -0,0 +1,128 @@
+use std::fmt;
+
+
+pub enum Json {
+    Null,
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0);
+        text
+    }
+
+    fn is_container(&self) -> bool {
+        matches!(self, Json::Array(_) | Json::Object(_))
+    }
+
+    fn write_pretty(&self, text: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        match self {
+            Json::Array(values) if values.iter().any(Json::is_container) => {
+                text.push_str("[\n");
+                for (index, value) in values.iter().enumerate() {
+                    text.push_str(&indent);
+                    value.write_pretty(text, depth + 1);
+                    text.push_str(if index + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                text.push_str(&"  ".repeat(depth));
+                text.push(']');
+            },
+            Json::Object(fields) if !fields.is_empty() => {
+                text.push_str("{\n");
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    text.push_str(&format!("{}{}: ", indent, escape(key)));
+                    value.write_pretty(text, depth + 1);
+                    text.push_str(if index + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                text.push_str(&"  ".repeat(depth));
+                text.push('}');
+            },
+            _ => text.push_str(&self.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Json {
+        Json::Number(value as i64)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Number(value) => write!(f, "{}", value),
+            Json::String(value) => write!(f, "{}", escape(value)),
+            Json::Array(values) => {
+                let values: Vec<String> = values.iter().map(Json::to_string).collect();
+                write!(f, "[{}]", values.join(","))
+            },
+            Json::Object(fields) => {
+                let fields: Vec<String> = fields.iter()
+                    .map(|(key, value)| format!("{}:{}", escape(key), value))
+                    .collect();
+                write!(f, "{{{}}}", fields.join(","))
+            },
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Json;
+
+    #[test]
+    fn test_display() {
+        let json = Json::object(vec![
+            ("name", Json::from("say \"hi\"\n")),
+            ("values", Json::Array(vec![Json::from(1), Json::Number(-2)])),
+        ]);
+
+        assert_eq!(json.to_string(), r#"{"name":"say \"hi\"\n","values":[1,-2]}"#);
+    }
+
+    #[test]
+    fn test_to_pretty_string() {
+        let json = Json::object(vec![
+            ("values", Json::Array(vec![Json::from(1), Json::from(2)])),
+            ("objects", Json::Array(vec![Json::object(vec![("empty", Json::Array(Vec::new()))])])),
+        ]);
+
+        assert_eq!(json.to_pretty_string(),
+            "{\n  \"values\": [1,2],\n  \"objects\": [\n    {\n      \"empty\": []\n    }\n  ]\n}");
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use crate::{assembler::{AsmLine, SymbolTable}, source_map::SourceMapping};
+
+
+pub fn get_listing(
+    asm_text: &str, mappings: &[SourceMapping], vm_texts: &HashMap<String, String>) -> String {
+
+    let symbol_table = SymbolTable::new(asm_text);
+    let mut owners: Vec<Option<&SourceMapping>> = vec![None; asm_text.lines().count() + 1];
+    for mapping in mappings {
+        owners[mapping.asm_start_line..=mapping.asm_end_line].fill(Some(mapping));
+    }
+
+    let mut lines = Vec::new();
+    let mut current_owner = None;
+    let mut rom_address = 0;
+
+    for (index, text) in asm_text.lines().enumerate() {
+        let Some(asm_line) = AsmLine::parse(text) else {
+            continue;
+        };
+        let owner = owners[index + 1];
+        if current_owner != Some(owner) {
+            lines.push(get_heading(owner, vm_texts));
+            current_owner = Some(owner);
+        }
+
+        let (address, instruction, symbol) = match asm_line {
+            AsmLine::Label(label) => (String::new(), format!("({})", label), Some(label)),
+            AsmLine::Address(value) => {
+                rom_address += 1;
+                let symbol = value.parse::<u16>().is_err().then_some(value);
+                ((rom_address - 1).to_string(), format!("@{}", value), symbol)
+            },
+            AsmLine::Compute(compute) => {
+                rom_address += 1;
+                ((rom_address - 1).to_string(), compute.to_string(), None)
+            },
+        };
+        lines.push(match symbol.and_then(|symbol| symbol_table.get_address(symbol)) {
+            Some(value) => format!("{:>7}  {:<40}= {}", address, instruction, value),
+            None => format!("{:>7}  {}", address, instruction).trim_end().to_string(),
+        });
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn get_heading(mapping: Option<&SourceMapping>, vm_texts: &HashMap<String, String>) -> String {
+    let Some(mapping) = mapping else {
+        return String::from("(generated)");
+    };
+    let source_line = vm_texts.get(&mapping.vm_file)
+        .and_then(|vm_text| vm_text.lines().nth(mapping.line_number - 1))
+        .unwrap_or_default();
+    format!("{}:{}  {}", mapping.vm_file, mapping.line_number, source_line.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::source_map::SourceMapping;
+
+    use super::get_listing;
+
+    #[test]
+    fn test_get_listing() {
+        let asm_text = "// bootstrap\n  @256\n  D=A\n// > Main.asm\n// function Main.main 0\n(Main.main)\n\
+            // push static 3\n  @Main.3\n  D=M\n// goto Main.main\n  @Main.main\n  0;JMP\n";
+        let mapping = |line_number, asm_lines: (usize, usize), rom: (usize, usize)| SourceMapping {
+            vm_file: String::from("Main.vm"),
+            line_number,
+            function_name: String::from("Main.main"),
+            asm_start_line: asm_lines.0,
+            asm_end_line: asm_lines.1,
+            rom_start: rom.0,
+            rom_end: rom.1,
+        };
+        let mappings = vec![mapping(1, (5, 6), (2, 2)), mapping(3, (7, 9), (2, 4)), mapping(4, (10, 12), (4, 6))];
+        let vm_texts = HashMap::from([(
+            String::from("Main.vm"),
+            String::from("function Main.main 0\n// read\n  push static 3 // x\ngoto Main.main"),
+        )]);
+
+        assert_eq!(get_listing(asm_text, &mappings, &vm_texts), "\
+(generated)
+      0  @256
+      1  D=A
+Main.vm:1  function Main.main 0
+         (Main.main)                             = 2
+Main.vm:3  push static 3 // x
+      2  @Main.3                                 = 16
+      3  D=M
+Main.vm:4  goto Main.main
+      4  @Main.main                              = 2
+      5  0;JMP
+");
+    }
+}
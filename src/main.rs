@@ -1,102 +1,359 @@
-use std::{path::{Path, PathBuf}, env, fs::{OpenOptions, self}, io::Write};
+use std::{fs, io::{self, IsTerminal}, path::Path, process};
 
-use clap::{command, Arg, ArgAction};
+use analysis::analyze;
+use asm_comment::CommentLevel;
+use assembler::{assemble, get_hack_text};
+use budget::{check_rom_budget, check_static_budget, ROM_SIZE};
+use clap::{builder::PossibleValuesParser, command, Arg, ArgAction, ArgMatches};
+use call_graph::CallGraph;
+use cfg::get_program_dot;
 use code_writer::CodeWriter;
+use diagnostics::{
+    format::{to_json, to_sarif, MessageFormat}, registry::{find_lint, Level, Lint, LINTS}, render::render,
+    Diagnostic, LintLevels, Severity,
+};
+use optimizer::{
+    compact_frame::find_compact_frame_functions, dead_code::eliminate_dead_functions,
+    inline::inline_functions,
+};
+use parser::{Command, CommandType, Parser};
+use formatter::format_vm;
 use glob::glob;
-use parser::{Parser, CommandType};
+use listing::get_listing;
+use program::{Program, VmFile};
+use stats::get_stats_text;
+use symbols::{get_symbol_entries, get_symbols_text};
 use util::load_text;
 
 mod parser;
 mod code_writer;
+mod analysis;
+mod asm_comment;
+mod assembler;
+mod budget;
+mod call_graph;
+mod cfg;
+mod diagnostics;
+mod formatter;
+mod json;
+mod listing;
+mod optimizer;
+mod program;
+mod source_map;
+mod stats;
+mod symbols;
 mod util;
 
-fn translate(input_path: &Path, need_bootstrap: bool) {
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Stage {
+    Tokens,
+    Ast,
+    Ir,
+    Asm,
+    Hack,
+}
+
+#[derive(Default)]
+struct Options {
+    need_bootstrap: bool,
+    keep_functions: Vec<String>,
+    inline_threshold: usize,
+    optimize_tail_calls: bool,
+    compact_frames: bool,
+    lint_levels: LintLevels,
+    message_format: MessageFormat,
+    source_map: bool,
+    listing: bool,
+    emit: Vec<String>,
+    max_rom: Option<usize>,
+    stats: bool,
+    comment_level: CommentLevel,
+    vm_comments: bool,
+    comment_locations: bool,
+}
+
+fn translate(input_path: &Path, options: &Options) -> Result<(), String> {
     if input_path.is_file() {
-        translate_file(input_path);
+        translate_file(input_path, options)
     } else if input_path.is_dir() {
-        translate_folder(input_path, need_bootstrap);
+        translate_folder(input_path, options)
+    } else {
+        Err(format!("Cannot find '{}'", input_path.display()))
     }
 }
 
-fn translate_file(input_path: &Path) -> PathBuf {
+fn translate_file(input_path: &Path, options: &Options) -> Result<(), String> {
     let folder_path = input_path.parent().unwrap();
-    let file_base_name = input_path.file_stem().unwrap()
-        .to_string_lossy().to_string();
-    let output_path_str = folder_path.join(format!("{}.asm", &file_base_name))
+    let vm_file = VmFile::load(input_path)
+        .map_err(|diagnostics| report_diagnostics(&diagnostics, options).unwrap_err())?;
+    let output_path_str = folder_path.join(format!("{}.asm", &vm_file.name))
         .to_string_lossy().to_string();
-    let input_text = load_text(input_path);
+    let program = Program { files: vec![vm_file] };
+    write_source_outputs(&program, &output_path_str, options);
+    if get_last_stage(options) < Stage::Ir {
+        return Ok(());
+    }
+
+    check_program(&program, false, options)?;
+    let program_outputs = get_program_outputs(&program, options);
+    write_ir_output(&program, &output_path_str, options);
+    if get_last_stage(options) < Stage::Asm {
+        return Ok(());
+    }
 
-    let mut parser = Parser::new(&input_text);
     let mut code_writer = CodeWriter::new(&output_path_str);
+    code_writer.set_comment_level(options.comment_level);
+    code_writer.set_vm_comments(options.vm_comments, options.comment_locations);
+    write_commands(&mut code_writer, &program.files[0].commands, options);
+    check_budgets(&code_writer, &output_path_str, options)?;
+    write_outputs(&code_writer, &output_path_str, &program, &program_outputs, options)?;
+    print_stats(&code_writer, options);
+    Ok(())
+}
 
-    while parser.has_more_lines() {
-        parser.advance();
+fn translate_folder(input_folder: &Path, options: &Options) -> Result<(), String> {
+    let mut program = Program::load_folder(input_folder)
+        .map_err(|diagnostics| report_diagnostics(&diagnostics, options).unwrap_err())?;
+    let input_folder_name = input_folder.file_stem().unwrap().to_string_lossy().to_string();
+    let out_file_path = input_folder.join(format!("{}.asm", input_folder_name));
+    let out_file_path_str = out_file_path.to_string_lossy().to_string();
+    write_source_outputs(&program, &out_file_path_str, options);
+    if get_last_stage(options) < Stage::Ir {
+        return Ok(());
+    }
 
-        match parser.command_type() {
-            CommandType::Arithmetic => {
-                code_writer.write_arithmetic(parser.arg1());
-            }
-            CommandType::Push => {
-                code_writer.write_push_pop("push", parser.arg1(), parser.arg2());
-            }
-            CommandType::Pop => {
-                code_writer.write_push_pop("pop", parser.arg1(), parser.arg2());
-            }
-            CommandType::Label => {
-                code_writer.write_label(parser.arg1());
-            }
-            CommandType::Goto => {
-                code_writer.write_goto(parser.arg1());
-            }
-            CommandType::If => {
-                code_writer.write_if(parser.arg1());
-            }
-            CommandType::Function => {
-                code_writer.write_function(parser.arg1(), parser.arg2());
-            }
-            CommandType::Call => {
-                code_writer.write_call(parser.arg1(), parser.arg2());
-            }
-            CommandType::Return => {
-                code_writer.write_return();
-            }
+    check_program(&program, true, options)?;
+    let program_outputs = get_program_outputs(&program, options);
+
+    if options.inline_threshold > 0 {
+        let inlined_calls = inline_functions(&mut program, options.inline_threshold);
+        for inlined_call in inlined_calls {
+            print_status(options, &format!("Inlined '{}' into '{}'", inlined_call.callee, inlined_call.caller));
+        }
+    }
+    if options.need_bootstrap {
+        let removed = eliminate_dead_functions(
+            &mut program, "Sys.init", &options.keep_functions);
+        for function_name in removed {
+            print_status(options, &format!("Removed unreachable function '{}'", function_name));
         }
     }
-    Path::new(&output_path_str).to_path_buf()
+
+    write_ir_output(&program, &out_file_path_str, options);
+    if get_last_stage(options) < Stage::Asm {
+        return Ok(());
+    }
+
+    let mut code_writer = CodeWriter::new(&out_file_path_str);
+    code_writer.set_comment_level(options.comment_level);
+    code_writer.set_vm_comments(options.vm_comments, options.comment_locations);
+    if options.compact_frames {
+        code_writer.set_compact_frame_functions(find_compact_frame_functions(&program));
+    }
+    if options.need_bootstrap {
+        code_writer.write_bootstrap();
+    }
+
+    for vm_file in &program.files {
+        code_writer.set_file_name(&vm_file.name);
+        write_commands(&mut code_writer, &vm_file.commands, options);
+    }
+
+    check_budgets(&code_writer, &out_file_path_str, options)?;
+    write_outputs(&code_writer, &out_file_path_str, &program, &program_outputs, options)?;
+    print_stats(&code_writer, options);
+
+    if options.compact_frames {
+        print_status(options, &format!(
+            "Compact frames saved {} instructions", code_writer.get_saved_instruction_count()));
+    }
+    Ok(())
 }
 
-fn translate_folder(input_folder: &Path, need_bootstrap: bool) {
-    let pattern = input_folder.join("*.vm").to_string_lossy().to_string();
-    let vm_files = glob(&pattern).unwrap();
-    let asm_files = vm_files
-        .map(|vm_file| translate_file(vm_file.unwrap().as_path()))
-        .collect::<Vec<PathBuf>>();
+fn check_budgets(code_writer: &CodeWriter, asm_path_str: &str, options: &Options) -> Result<(), String> {
+    let max_rom = options.max_rom.unwrap_or(ROM_SIZE);
+    check_rom_budget(code_writer.get_rom_size(), code_writer.get_source_mappings(), max_rom)
+        .and_then(|()| check_static_budget(code_writer.get_symbols()))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(asm_path_str);
+        })
+}
 
-    let input_folder_name = input_folder.file_stem().unwrap().to_string_lossy().to_string();
-    let out_file_path = input_folder.join(format!("{}.asm", input_folder_name));
-    let out_file_path_str = out_file_path.to_string_lossy().to_string();
+fn print_stats(code_writer: &CodeWriter, options: &Options) {
+    if options.stats {
+        print_status(options, &get_stats_text(code_writer.get_command_stats(), code_writer.get_rom_size()));
+    }
+}
 
-    {
-        let mut code_writer = CodeWriter::new(&out_file_path_str);
-        if need_bootstrap {
-            code_writer.write_bootstrap();
+fn get_last_stage(options: &Options) -> Stage {
+    let needs_asm = options.emit.is_empty() || options.source_map || options.listing || options.stats;
+    options.emit.iter()
+        .map(|kind| match kind.as_str() {
+            "tokens" => Stage::Tokens,
+            "ast" => Stage::Ast,
+            "ir" => Stage::Ir,
+            "hack" => Stage::Hack,
+            _ => Stage::Asm,
+        })
+        .chain(needs_asm.then_some(Stage::Asm))
+        .max()
+        .unwrap()
+}
+
+fn write_source_outputs(program: &Program, asm_path_str: &str, options: &Options) {
+    for kind in &options.emit {
+        let (extension, text) = match kind.as_str() {
+            "tokens" => ("tokens", get_tokens_text(program)),
+            "ast" => ("ast.json", program::to_json(program).to_pretty_string()),
+            _ => continue,
+        };
+        fs::write(Path::new(asm_path_str).with_extension(extension), text).unwrap();
+    }
+}
+
+fn get_tokens_text(program: &Program) -> String {
+    let mut lines = Vec::new();
+    for vm_file in &program.files {
+        let text = load_text(Path::new(&vm_file.path));
+        for (line_number, column, token) in Parser::new(&text).tokens() {
+            lines.push(format!("{}.vm:{}:{} {}\n", vm_file.name, line_number, column, token));
         }
     }
-    
-    let mut out_file = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(out_file_path)
-        .unwrap();
+    lines.concat()
+}
 
-    for asm_file_path in &asm_files {
-        let asm_file_name = asm_file_path.file_name().unwrap().to_string_lossy().to_string();
-        let asm_text = load_text(asm_file_path);
-        let final_text = format!("// > {}\n{}", asm_file_name, asm_text);
+fn write_ir_output(program: &Program, asm_path_str: &str, options: &Options) {
+    if options.emit.iter().any(|kind| kind == "ir") {
+        fs::write(Path::new(asm_path_str).with_extension("ir"), program::to_vm_text(program)).unwrap();
+    }
+}
 
-        out_file.write_all(final_text.as_bytes()).unwrap();
-        fs::remove_file(asm_file_path).unwrap();
+fn get_program_outputs(program: &Program, options: &Options) -> Vec<(&'static str, String)> {
+    let mut outputs = Vec::new();
+    for kind in &options.emit {
+        match kind.as_str() {
+            "callgraph" => outputs.push(("callgraph.dot", get_call_graph_dot(program, options))),
+            "cfg" => outputs.push(("cfg.dot", get_program_dot(program))),
+            _ => {},
+        }
     }
+    outputs
+}
+
+fn get_call_graph_dot(program: &Program, options: &Options) -> String {
+    let call_graph = CallGraph::new(program);
+    let reachable = if options.need_bootstrap {
+        call_graph.live_functions("Sys.init", &options.keep_functions)
+    } else {
+        None
+    };
+    call_graph.to_dot(reachable.as_ref())
+}
+
+fn write_outputs(
+    code_writer: &CodeWriter, asm_path_str: &str, program: &Program,
+    program_outputs: &[(&str, String)], options: &Options) -> Result<(), String> {
+
+    let asm_path = Path::new(asm_path_str);
+    if options.source_map {
+        let asm_file_name = asm_path.file_name().unwrap().to_string_lossy();
+        let source_map = source_map::to_json(&asm_file_name, code_writer.get_source_mappings());
+        fs::write(format!("{}.map", asm_path_str), source_map.to_pretty_string()).unwrap();
+    }
+    for (extension, text) in program_outputs {
+        fs::write(asm_path.with_extension(extension), text).unwrap();
+    }
+    if !options.listing && !options.emit.iter().any(|kind| kind.starts_with("symbols") || kind == "hack") {
+        return Ok(());
+    }
+
+    let asm_text = load_text(asm_path);
+    if options.listing {
+        let vm_texts = program.files.iter()
+            .map(|vm_file| (format!("{}.vm", vm_file.name), load_text(&vm_file.path)))
+            .collect();
+        let listing = get_listing(&asm_text, code_writer.get_source_mappings(), &vm_texts);
+        fs::write(asm_path.with_extension("lst"), listing).unwrap();
+    }
+
+    let symbol_entries = get_symbol_entries(code_writer.get_symbols(), &asm_text);
+    for kind in &options.emit {
+        let (extension, text) = match kind.as_str() {
+            "symbols" => ("sym", get_symbols_text(&symbol_entries)),
+            "symbols-json" => ("sym.json", symbols::to_json(&symbol_entries).to_pretty_string()),
+            "hack" => ("hack", get_hack_text(&assemble(&asm_text)?)),
+            _ => continue,
+        };
+        fs::write(asm_path.with_extension(extension), text).unwrap();
+    }
+    Ok(())
+}
+
+fn check_program(program: &Program, is_whole_program: bool, options: &Options) -> Result<(), String> {
+    let diagnostics = options.lint_levels.apply(analyze(program, is_whole_program));
+    report_diagnostics(&diagnostics, options)
+}
+
+fn report_diagnostics(diagnostics: &[Diagnostic], options: &Options) -> Result<(), String> {
+    match options.message_format {
+        MessageFormat::Human => {
+            let use_color = io::stderr().is_terminal();
+            for diagnostic in diagnostics {
+                eprintln!("{}", render(diagnostic, use_color));
+            }
+        },
+        MessageFormat::Json => {
+            for diagnostic in diagnostics {
+                println!("{}", to_json(diagnostic));
+            }
+        },
+        MessageFormat::Sarif => println!("{}", to_sarif(diagnostics).to_pretty_string()),
+    }
+
+    let error_count = diagnostics.iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    if error_count > 0 {
+        Err(format!("Translation aborted due to {} error(s)", error_count))
+    } else {
+        Ok(())
+    }
+}
+
+fn print_status(options: &Options, message: &str) {
+    if options.message_format == MessageFormat::Human {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+fn write_commands(code_writer: &mut CodeWriter, commands: &[Command], options: &Options) {
+    let mut in_function = false;
+    let mut index = 0;
+
+    while index < commands.len() {
+        let command = &commands[index];
+        in_function |= command.get_command_type() == &CommandType::Function;
+
+        if options.optimize_tail_calls && in_function && is_tail_call(&commands[index..]) {
+            code_writer.set_line_number(command.get_line_number());
+            code_writer.write_vm_comments(command);
+            code_writer.write_vm_comments(&commands[index + 1]);
+            code_writer.write_tail_call(command.get_arg1(), command.get_arg2().unwrap());
+            index += 2;
+        } else {
+            code_writer.write_command(command);
+            index += 1;
+        }
+    }
+}
+
+fn is_tail_call(commands: &[Command]) -> bool {
+    matches!(
+        commands.iter().map(Command::get_command_type).take(2).collect::<Vec<_>>().as_slice(),
+        [CommandType::Call, CommandType::Return]
+    )
 }
 
 
@@ -104,25 +361,264 @@ fn main() {
     let matches = command!()
         .arg(Arg::new("input_path")
              .help("Path of vm file to be translated")
-             .required(true))
+             .required_unless_present("explain"))
         .arg(Arg::new("no_bootstrap")
              .long("no-bootstrap")
              .action(ArgAction::SetTrue)
              .help("Do not make bootstrap codes"))
+        .arg(Arg::new("keep")
+             .long("keep")
+             .value_name("FUNCTIONS")
+             .value_delimiter(',')
+             .action(ArgAction::Append)
+             .help("Functions to keep even if they are not reachable from Sys.init"))
+        .arg(Arg::new("inline_threshold")
+             .long("inline-threshold")
+             .value_name("COMMANDS")
+             .value_parser(clap::value_parser!(usize))
+             .default_value("0")
+             .help("Inline non-recursive functions with at most this many commands"))
+        .arg(Arg::new("optimize_tail_calls")
+             .long("optimize-tail-calls")
+             .action(ArgAction::SetTrue)
+             .help("Reuse the caller's frame for a call followed by return"))
+        .arg(Arg::new("compact_frames")
+             .long("compact-frames")
+             .action(ArgAction::SetTrue)
+             .help("Do not save THIS and THAT when calling functions that never change them"))
+        .arg(Arg::new("source_map")
+             .long("source-map")
+             .action(ArgAction::SetTrue)
+             .help("Write a .asm.map file mapping assembly lines and ROM addresses to VM lines"))
+        .arg(Arg::new("listing")
+             .long("listing")
+             .action(ArgAction::SetTrue)
+             .help("Write a .lst file showing each VM line with its Hack instructions and ROM addresses"))
+        .arg(Arg::new("emit")
+             .long("emit")
+             .value_name("KINDS")
+             .value_parser(["tokens", "ast", "ir", "asm", "hack", "symbols", "symbols-json", "callgraph", "cfg"])
+             .value_delimiter(',')
+             .action(ArgAction::Append)
+             .help("Write the given pipeline stages and additional outputs next to the .asm file"))
+        .arg(Arg::new("max_rom")
+             .long("max-rom")
+             .value_name("INSTRUCTIONS")
+             .value_parser(clap::value_parser!(u64).range(1..=ROM_SIZE as u64))
+             .help("Fail if the program needs more ROM than this [default: 32768]"))
+        .arg(Arg::new("stats")
+             .long("stats")
+             .action(ArgAction::SetTrue)
+             .help("Print the VM commands and Hack instructions of each file and function"))
+        .arg(Arg::new("comments")
+             .long("comments")
+             .value_name("LEVEL")
+             .value_parser(["none", "commands", "full"])
+             .default_value("commands")
+             .help("Write no comments, a comment per vm command, or also its stack effect and operands"))
+        .arg(Arg::new("vm_comments")
+             .long("vm-comments")
+             .action(ArgAction::SetTrue)
+             .help("Copy the comments of the vm files above the assembly of the commands they precede"))
+        .arg(Arg::new("comment_locations")
+             .long("comment-locations")
+             .action(ArgAction::SetTrue)
+             .requires("vm_comments")
+             .help("Prefix the copied comments with their vm file and line"))
+        .arg(Arg::new("allow")
+             .short('A')
+             .long("allow")
+             .value_name("LINT")
+             .value_parser(PossibleValuesParser::new(LINTS.map(|lint| lint.name)))
+             .value_delimiter(',')
+             .action(ArgAction::Append)
+             .help("Do not report the given lints"))
+        .arg(Arg::new("warn")
+             .short('W')
+             .long("warn")
+             .value_name("LINT")
+             .value_parser(PossibleValuesParser::new(LINTS.map(|lint| lint.name)))
+             .value_delimiter(',')
+             .action(ArgAction::Append)
+             .help("Report the given lints as warnings"))
+        .arg(Arg::new("deny")
+             .short('D')
+             .long("deny")
+             .value_name("LINT")
+             .value_parser(PossibleValuesParser::new(LINTS.map(|lint| lint.name)))
+             .value_delimiter(',')
+             .action(ArgAction::Append)
+             .help("Report the given lints as errors"))
+        .arg(Arg::new("deny_warnings")
+             .long("deny-warnings")
+             .action(ArgAction::SetTrue)
+             .help("Report all warnings as errors"))
+        .arg(Arg::new("message_format")
+             .long("message-format")
+             .value_name("FORMAT")
+             .value_parser(["human", "json", "sarif"])
+             .default_value("human")
+             .help("Print diagnostics for people, as JSON lines or as a SARIF 2.1 log on stdout"))
+        .arg(Arg::new("explain")
+             .long("explain")
+             .value_name("LINT")
+             .exclusive(true)
+             .help("Print a description of the given lint and exit"))
+        .subcommand_negates_reqs(true)
+        .args_conflicts_with_subcommands(true)
+        .subcommand(clap::Command::new("fmt")
+             .about("Rewrite vm files in the canonical format")
+             .arg(Arg::new("paths")
+                  .help("Paths of vm files or folders to be formatted")
+                  .required(true)
+                  .num_args(1..))
+             .arg(Arg::new("check")
+                  .long("check")
+                  .action(ArgAction::SetTrue)
+                  .help("Do not write the files but fail if any of them is not formatted")))
         .get_matches();
 
+    if let Some(fmt_matches) = matches.subcommand_matches("fmt") {
+        if let Err(message) = format_paths(&get_values(fmt_matches, "paths"), fmt_matches.get_flag("check")) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(name) = matches.get_one::<String>("explain") {
+        match find_lint(name) {
+            Some(lint) => print!("{}", explain_lint(lint)),
+            None => {
+                eprintln!("Unknown lint '{}'", name);
+                process::exit(1);
+            },
+        }
+        return;
+    }
+
+    let lint_levels = get_lint_levels(&matches).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    });
     let input_path_str = matches.get_one::<String>("input_path").unwrap();
-    let need_bootstrap = !matches.get_flag("no_bootstrap");
+    let options = Options {
+        need_bootstrap: !matches.get_flag("no_bootstrap"),
+        keep_functions: get_values(&matches, "keep"),
+        inline_threshold: *matches.get_one::<usize>("inline_threshold").unwrap(),
+        optimize_tail_calls: matches.get_flag("optimize_tail_calls"),
+        compact_frames: matches.get_flag("compact_frames"),
+        source_map: matches.get_flag("source_map"),
+        listing: matches.get_flag("listing"),
+        emit: get_values(&matches, "emit"),
+        stats: matches.get_flag("stats"),
+        comment_level: match matches.get_one::<String>("comments").unwrap().as_str() {
+            "none" => CommentLevel::None,
+            "full" => CommentLevel::Full,
+            _ => CommentLevel::Commands,
+        },
+        vm_comments: matches.get_flag("vm_comments"),
+        comment_locations: matches.get_flag("comment_locations"),
+        max_rom: matches.get_one::<u64>("max_rom").map(|max_rom| *max_rom as usize),
+        lint_levels,
+        message_format: match matches.get_one::<String>("message_format").unwrap().as_str() {
+            "json" => MessageFormat::Json,
+            "sarif" => MessageFormat::Sarif,
+            _ => MessageFormat::Human,
+        },
+    };
+
+    print_status(&options, &format!("Start translating for '{}", input_path_str));
+    if let Err(message) = translate(Path::new(input_path_str), &options) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+    print_status(&options, "Completed");
+}
+
+fn format_paths(input_paths: &[String], check: bool) -> Result<(), String> {
+    let mut vm_paths = Vec::new();
+    for input_path_str in input_paths {
+        let input_path = Path::new(input_path_str);
+        if input_path.is_dir() {
+            let pattern = input_path.join("*.vm").to_string_lossy().to_string();
+            vm_paths.extend(glob(&pattern).unwrap().map(Result::unwrap));
+        } else if input_path.is_file() {
+            vm_paths.push(input_path.to_path_buf());
+        } else {
+            return Err(format!("Cannot find '{}'", input_path.display()));
+        }
+    }
 
-    println!("Start translating for '{}", input_path_str);
-    translate(Path::new(input_path_str), need_bootstrap);
-    println!("Completed");
+    let mut unformatted_count = 0;
+    for vm_path in vm_paths {
+        let text = load_text(&vm_path);
+        let name = vm_path.file_stem().unwrap().to_string_lossy();
+        let vm_file = VmFile::parse(&name, &vm_path.to_string_lossy(), &text)
+            .map_err(|diagnostics| report_diagnostics(&diagnostics, &Options::default()).unwrap_err())?;
+
+        let mut formatted = format_vm(&vm_file);
+        if text.contains("\r\n") {
+            formatted = formatted.replace('\n', "\r\n");
+        }
+        if formatted == text {
+            continue;
+        }
+        unformatted_count += 1;
+        if check {
+            println!("Would reformat '{}'", vm_path.display());
+        } else {
+            fs::write(&vm_path, formatted).unwrap();
+            println!("Reformatted '{}'", vm_path.display());
+        }
+    }
+
+    if check && unformatted_count > 0 {
+        Err(format!("{} file(s) would be reformatted", unformatted_count))
+    } else {
+        Ok(())
+    }
+}
+
+fn get_lint_levels(matches: &ArgMatches) -> Result<LintLevels, String> {
+    let mut levels = Vec::new();
+    for (id, level) in [("allow", Level::Allow), ("warn", Level::Warn), ("deny", Level::Deny)] {
+        if let (Some(indices), Some(names)) = (matches.indices_of(id), matches.get_many::<String>(id)) {
+            levels.extend(indices.zip(names).map(|(index, name)| (index, name, level)));
+        }
+    }
+    levels.sort_by_key(|(index, _, _)| *index);
+
+    let mut lint_levels = LintLevels::default();
+    lint_levels.set_deny_warnings(matches.get_flag("deny_warnings"));
+    for (_, name, level) in levels {
+        lint_levels.set_level(name, level)?;
+    }
+    Ok(lint_levels)
+}
+
+fn explain_lint(lint: &Lint) -> String {
+    let default_level = match lint.default_level {
+        Level::Allow => "allow",
+        Level::Warn => "warn",
+        Level::Deny => "deny",
+        Level::Forbid => "always an error",
+    };
+    format!("{}: {}\nDefault level: {}\n\n{}\n", lint.name, lint.summary, default_level, lint.explanation)
+}
+
+fn get_values(matches: &ArgMatches, id: &str) -> Vec<String> {
+    matches.get_many::<String>(id)
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs, path::Path};
-    use crate::{translate, util::load_text};
+    use crate::{
+        diagnostics::{registry::Level, LintLevels}, format_paths, translate, util::load_text, Options,
+    };
 
     #[test]
     fn test_main_given_stack_commands() {
@@ -144,12 +640,168 @@ mod tests {
         test_vm("TestInternalSymbol");
     }
 
+    #[test]
+    fn test_main_given_unreachable_functions() {
+        test_vm_with_options("TestDeadCode", &Options {
+            need_bootstrap: true,
+            keep_functions: vec![String::from("Main.handler")],
+            ..Options::default()
+        });
+    }
+
+    #[test]
+    fn test_main_given_inline_threshold() {
+        test_vm_with_options("TestInline", &Options {
+            need_bootstrap: true,
+            inline_threshold: 12,
+            ..Options::default()
+        });
+    }
+
+    #[test]
+    fn test_main_given_tail_calls() {
+        test_vm_with_options("TestTailCall", &Options {
+            need_bootstrap: true,
+            optimize_tail_calls: true,
+            ..Options::default()
+        });
+    }
+
+    #[test]
+    fn test_main_given_compact_frames() {
+        test_vm_with_options("TestCompactFrame", &Options {
+            need_bootstrap: true,
+            optimize_tail_calls: true,
+            compact_frames: true,
+            ..Options::default()
+        });
+    }
+
+    #[test]
+    fn test_main_given_undefined_label() {
+        let result = translate(Path::new("test_data/TestUndefinedLabel"), &Options::default());
+
+        assert_eq!(result, Err(String::from("Translation aborted due to 1 error(s)")));
+        assert!(!Path::new("test_data/TestUndefinedLabel/TestUndefinedLabel.asm").exists());
+    }
+
+    #[test]
+    fn test_main_given_source_stages() {
+        let result = translate(Path::new("test_data/TestUndefinedLabel"), &Options {
+            emit: vec![String::from("tokens"), String::from("ast")],
+            ..Options::default()
+        });
+        let tokens = fs::read_to_string("test_data/TestUndefinedLabel/TestUndefinedLabel.tokens");
+        let ast_exists = Path::new("test_data/TestUndefinedLabel/TestUndefinedLabel.ast.json").exists();
+        let _ = fs::remove_file("test_data/TestUndefinedLabel/TestUndefinedLabel.tokens");
+        let _ = fs::remove_file("test_data/TestUndefinedLabel/TestUndefinedLabel.ast.json");
+
+        assert_eq!(result, Ok(()));
+        assert!(tokens.unwrap().starts_with("Main.vm:2:1 function\nMain.vm:2:10 Main.main\n"));
+        assert!(ast_exists);
+        assert!(!Path::new("test_data/TestUndefinedLabel/TestUndefinedLabel.asm").exists());
+    }
+
+    #[test]
+    fn test_main_given_rom_budget() {
+        let result = translate(Path::new("test_data/TestRomBudget"), &Options {
+            need_bootstrap: true,
+            max_rom: Some(80),
+            ..Options::default()
+        });
+
+        assert_eq!(result, Err(String::from("\
+ROM budget exceeded: 88 instructions were emitted but the budget is 80
+  (generated)                                 53
+  Sys.vm                                      35
+    Sys.init                                  35")));
+        assert!(!Path::new("test_data/TestRomBudget/TestRomBudget.asm").exists());
+    }
+
+    #[test]
+    fn test_main_given_denied_lint() {
+        let mut lint_levels = LintLevels::default();
+        lint_levels.set_level("unused_function", Level::Deny).unwrap();
+        let result = translate(Path::new("test_data/TestFolder"), &Options {
+            lint_levels,
+            ..Options::default()
+        });
+
+        assert_eq!(result, Err(String::from("Translation aborted due to 1 error(s)")));
+    }
+
+    #[test]
+    fn test_main_given_fmt_check() {
+        let folder = Path::new("test_data/TestFmtCheck");
+        let unformatted = "function Main.main 0\npush constant 1\nreturn\n";
+        let formatted = "function Main.main 0\n    push constant 1\n    return\n";
+        fs::create_dir_all(folder).unwrap();
+        fs::write(folder.join("Main.vm"), unformatted).unwrap();
+        fs::write(folder.join("Sys.vm"), "function Sys.init 0\n    call Main.main 0\n    return\n").unwrap();
+        let paths = [folder.to_string_lossy().to_string()];
+
+        let check_result = format_paths(&paths, true);
+        let check_text = load_text(folder.join("Main.vm"));
+        let format_result = format_paths(&paths, false);
+        let format_text = load_text(folder.join("Main.vm"));
+        let recheck_result = format_paths(&paths, true);
+        fs::remove_dir_all(folder).unwrap();
+
+        assert_eq!(check_result, Err(String::from("1 file(s) would be reformatted")));
+        assert_eq!(check_text, unformatted);
+        assert_eq!(format_result, Ok(()));
+        assert_eq!(format_text, formatted);
+        assert_eq!(recheck_result, Ok(()));
+    }
+
+    #[test]
+    fn test_main_given_fmt_crlf_file() {
+        let folder = Path::new("test_data/TestFmtCrlf");
+        fs::create_dir_all(folder).unwrap();
+        fs::write(folder.join("Main.vm"), "push constant 1\r\n").unwrap();
+        fs::write(folder.join("Sys.vm"), "function Sys.init 0\r\npush constant 1\r\nreturn\r\n").unwrap();
+        let paths = [folder.to_string_lossy().to_string()];
+
+        let check_result = format_paths(&paths, true);
+        let format_result = format_paths(&paths, false);
+        let main_text = fs::read_to_string(folder.join("Main.vm")).unwrap();
+        let sys_text = fs::read_to_string(folder.join("Sys.vm")).unwrap();
+        fs::remove_dir_all(folder).unwrap();
+
+        assert_eq!(check_result, Err(String::from("1 file(s) would be reformatted")));
+        assert_eq!(format_result, Ok(()));
+        assert_eq!(main_text, "push constant 1\r\n");
+        assert_eq!(sys_text, "function Sys.init 0\r\n    push constant 1\r\n    return\r\n");
+    }
+
+    #[test]
+    fn test_main_given_fmt_invalid_file() {
+        let folder = Path::new("test_data/TestFmtInvalid");
+        let text = "push constant 1\npop locl 0\n";
+        fs::create_dir_all(folder).unwrap();
+        fs::write(folder.join("Main.vm"), text).unwrap();
+        let paths = [folder.join("Main.vm").to_string_lossy().to_string()];
+
+        let result = format_paths(&paths, false);
+        let main_text = load_text(folder.join("Main.vm"));
+        fs::remove_dir_all(folder).unwrap();
+
+        assert_eq!(result, Err(String::from("Translation aborted due to 1 error(s)")));
+        assert_eq!(main_text, text);
+        assert_eq!(format_paths(&[String::from("test_data/Missing.vm")], false),
+            Err(String::from("Cannot find 'test_data/Missing.vm'")));
+    }
+
     fn test_vm(test_dest: &str) {
+        test_vm_with_options(test_dest, &Options::default());
+    }
+
+    fn test_vm_with_options(test_dest: &str, options: &Options) {
         let test_name = Path::new(test_dest).file_stem().unwrap()
             .to_string_lossy().to_string();
         let is_folder = test_name == test_dest;
 
-        translate(Path::new(&format!("test_data/{}", test_dest)), false);
+        translate(Path::new(&format!("test_data/{}", test_dest)), options).unwrap();
 
         let out_file_path = match is_folder {
             true => format!("test_data/{}/{}.asm", test_name, test_name),
@@ -157,7 +809,7 @@ mod tests {
         };
         
         let out = load_text(&out_file_path);
-        let solution = load_text(&format!("test_data/solution_{}.asm", test_name));
+        let solution = load_text(format!("test_data/solution_{}.asm", test_name));
 
         assert_eq!(out, solution);
         fs::remove_file(out_file_path).unwrap();
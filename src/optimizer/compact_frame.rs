@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use crate::{call_graph::CallGraph, parser::CommandType, program::Program};
+
+
+pub fn find_compact_frame_functions(program: &Program) -> HashSet<String> {
+    let call_graph = CallGraph::new(program);
+    let mut functions: HashSet<String> = program.files.iter()
+        .flat_map(|file| file.functions())
+        .filter(|function| !function.commands.iter().any(|command| {
+            command.get_command_type() == &CommandType::Pop && command.get_arg1() == "pointer"
+        }))
+        .map(|function| function.name.to_string())
+        .collect();
+
+    loop {
+        let writers: Vec<String> = functions.iter()
+            .filter(|function_name| call_graph.callees(function_name).iter()
+                .any(|callee| !functions.contains(callee)))
+            .cloned()
+            .collect();
+        if writers.is_empty() {
+            return functions;
+        }
+        for function_name in writers {
+            functions.remove(&function_name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::Program;
+
+    use super::find_compact_frame_functions;
+
+    #[test]
+    fn test_find_compact_frame_functions() {
+        let program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Main.sum 0\nreturn
+                function Main.sum 0\ncall Math.add 0\nreturn
+                function Main.set 0\npush argument 0\npop pointer 1\ncall Main.sum 0\nreturn
+                function Main.get 0\ncall Main.set 1\nreturn
+                function Main.print 0\ncall Output.printInt 1\nreturn"),
+            ("Math", "function Math.add 0\npush constant 0\nreturn"),
+        ]);
+        let mut functions: Vec<String> = find_compact_frame_functions(&program).into_iter().collect();
+        functions.sort();
+
+        assert_eq!(functions, vec!["Main.main", "Main.sum", "Math.add"]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{call_graph::CallGraph, program::Program};
+
+
+pub fn eliminate_dead_functions(
+    program: &mut Program, entry_function: &str, keep_functions: &[String]) -> Vec<String> {
+
+    let Some(reachable) = CallGraph::new(program).live_functions(entry_function, keep_functions) else {
+        return Vec::new();
+    };
+
+    let mut removed = Vec::new();
+    for file in &mut program.files {
+        let mut commands = file.top_level_commands().to_vec();
+        for function in file.functions() {
+            if reachable.contains(function.name) {
+                commands.extend(function.commands.iter().cloned());
+            } else {
+                removed.push(function.name.to_string());
+            }
+        }
+        file.commands = commands;
+    }
+
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::Program;
+
+    use super::eliminate_dead_functions;
+
+    fn new_program() -> Program {
+        Program::new(&[
+            ("Sys", "function Sys.init 0\ncall Main.main 0\nreturn"),
+            ("Main", "function Main.main 0\nreturn\nfunction Main.unused 0\nreturn\nfunction Main.handler 0\nreturn"),
+        ])
+    }
+
+    #[test]
+    fn test_eliminate_dead_functions() {
+        let mut program = new_program();
+        let removed = eliminate_dead_functions(&mut program, "Sys.init", &[]);
+
+        assert_eq!(removed, vec!["Main.unused", "Main.handler"]);
+        assert_eq!(program.files[1].commands.len(), 2);
+    }
+
+    #[test]
+    fn test_eliminate_dead_functions_given_keep_list() {
+        let mut program = new_program();
+        let removed = eliminate_dead_functions(
+            &mut program, "Sys.init", &[String::from("Main.handler")]);
+
+        assert_eq!(removed, vec!["Main.unused"]);
+        assert_eq!(program.files[1].functions().len(), 2);
+    }
+
+    #[test]
+    fn test_eliminate_dead_functions_given_no_entry_function() {
+        let mut program = new_program();
+        let removed = eliminate_dead_functions(&mut program, "Other.init", &[]);
+
+        assert!(removed.is_empty());
+        assert_eq!(program.files[1].functions().len(), 3);
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use crate::{
+    analysis::stack_balance::analyze_stack, call_graph::CallGraph,
+    parser::{Command, CommandType}, program::Program,
+};
+
+
+struct Candidate {
+    file_name: String,
+    nlocals: i32,
+    body: Vec<Command>,
+    uses_static: bool,
+    max_argument: i32,
+    written_pointers: Vec<i32>,
+}
+
+pub struct InlinedCall {
+    pub caller: String,
+    pub callee: String,
+}
+
+pub fn inline_functions(program: &mut Program, threshold: usize) -> Vec<InlinedCall> {
+    let candidates = find_candidates(program, threshold);
+    let mut inlined_calls = Vec::new();
+
+    for file in &mut program.files {
+        let mut commands = file.top_level_commands().to_vec();
+        for function in file.functions() {
+            let nlocals = function.commands[0].get_arg2().unwrap();
+            let mut inliner = Inliner::new(function.name, &file.name, nlocals, &candidates);
+            let body = inliner.inline_body(&function.commands[1..]);
+            let nlocals = nlocals + inliner.extra_locals;
+
+            let function_command = &function.commands[0];
+            commands.push(Command::new(&format!("function {} {}", function.name, nlocals))
+                .with_line_number(function_command.get_line_number()));
+            commands.extend(body);
+            inlined_calls.extend(inliner.inlined_calls);
+        }
+        file.commands = commands;
+    }
+
+    inlined_calls
+}
+
+fn find_candidates(program: &Program, threshold: usize) -> HashMap<String, Candidate> {
+    let call_graph = CallGraph::new(program);
+    let mut candidates = HashMap::new();
+
+    for file in &program.files {
+        for function in file.functions() {
+            let body = &function.commands[1..];
+            let is_recursive = call_graph
+                .reachable_from(call_graph.callees(function.name))
+                .contains(function.name);
+
+            if body.len() > threshold
+                || is_recursive
+                || body.last().map(Command::get_command_type) != Some(&CommandType::Return)
+                || !analyze_stack(body).errors.is_empty() {
+                continue;
+            }
+
+            let segment_indices = |command_type: CommandType, segment: &str| -> Vec<i32> {
+                body.iter()
+                    .filter(|command| command.get_command_type() == &command_type
+                        && command.get_arg1() == segment)
+                    .map(|command| command.get_arg2().unwrap())
+                    .collect()
+            };
+            let uses_static = !segment_indices(CommandType::Push, "static").is_empty()
+                || !segment_indices(CommandType::Pop, "static").is_empty();
+            let max_argument = segment_indices(CommandType::Push, "argument").into_iter()
+                .chain(segment_indices(CommandType::Pop, "argument"))
+                .max()
+                .unwrap_or(-1);
+            let mut written_pointers = segment_indices(CommandType::Pop, "pointer");
+            written_pointers.sort();
+            written_pointers.dedup();
+
+            candidates.insert(function.name.to_string(), Candidate {
+                file_name: file.name.clone(),
+                nlocals: function.commands[0].get_arg2().unwrap(),
+                body: body.to_vec(),
+                uses_static,
+                max_argument,
+                written_pointers,
+            });
+        }
+    }
+
+    candidates
+}
+
+struct Inliner<'a> {
+    caller: &'a str,
+    file_name: &'a str,
+    nlocals: i32,
+    candidates: &'a HashMap<String, Candidate>,
+    inline_index: u32,
+    extra_locals: i32,
+    inlined_calls: Vec<InlinedCall>,
+}
+
+impl<'a> Inliner<'a> {
+    fn new(
+        caller: &'a str, file_name: &'a str, nlocals: i32,
+        candidates: &'a HashMap<String, Candidate>) -> Inliner<'a> {
+
+        Inliner {
+            caller,
+            file_name,
+            nlocals,
+            candidates,
+            inline_index: 1,
+            extra_locals: 0,
+            inlined_calls: Vec::new(),
+        }
+    }
+
+    fn inline_body(&mut self, body: &[Command]) -> Vec<Command> {
+        let mut commands = Vec::new();
+
+        for command in body {
+            match self.get_candidate(command) {
+                Some(candidate) => {
+                    let nargs = command.get_arg2().unwrap();
+                    let inlined_commands = self.get_inlined_commands(command.get_arg1(), candidate, nargs);
+                    commands.extend(inlined_commands.into_iter()
+                        .enumerate()
+                        .map(|(index, inlined_command)| inlined_command
+                            .with_line_number(command.get_line_number())
+                            .with_comments(if index == 0 { command.get_comments().to_vec() } else { Vec::new() })));
+                    self.inlined_calls.push(InlinedCall {
+                        caller: self.caller.to_string(),
+                        callee: command.get_arg1().to_string(),
+                    });
+                },
+                None => commands.push(command.clone()),
+            }
+        }
+
+        commands
+    }
+
+    fn get_candidate(&self, command: &Command) -> Option<&'a Candidate> {
+        if command.get_command_type() != &CommandType::Call || command.get_arg1() == self.caller {
+            return None;
+        }
+
+        self.candidates.get(command.get_arg1()).filter(|candidate| {
+            (!candidate.uses_static || candidate.file_name == self.file_name)
+                && candidate.max_argument < command.get_arg2().unwrap()
+        })
+    }
+
+    fn get_inlined_commands(
+        &mut self, callee: &str, candidate: &Candidate, nargs: i32) -> Vec<Command> {
+
+        let arguments_base = self.nlocals;
+        let locals_base = arguments_base + nargs;
+        let saved_base = locals_base + candidate.nlocals;
+        let label_prefix = format!("{}$inline.{}", callee, self.inline_index);
+        let end_label = format!("{}$END", label_prefix);
+        self.inline_index += 1;
+        self.extra_locals = self.extra_locals
+            .max(nargs + candidate.nlocals + candidate.written_pointers.len() as i32);
+
+        let mut commands = Vec::new();
+
+        for index in (0..nargs).rev() {
+            commands.push(Command::new(&format!("pop local {}", arguments_base + index)));
+        }
+        for index in 0..candidate.nlocals {
+            commands.push(Command::new("push constant 0"));
+            commands.push(Command::new(&format!("pop local {}", locals_base + index)));
+        }
+        for (offset, pointer) in candidate.written_pointers.iter().enumerate() {
+            commands.push(Command::new(&format!("push pointer {}", pointer)));
+            commands.push(Command::new(&format!("pop local {}", saved_base + offset as i32)));
+        }
+
+        let last_index = candidate.body.len() - 1;
+        let mut needs_end_label = false;
+        for (index, command) in candidate.body.iter().enumerate() {
+            match (command.get_command_type(), command.get_arg1()) {
+                (CommandType::Push, segment @ ("argument" | "local")) => {
+                    let base = if segment == "argument" { arguments_base } else { locals_base };
+                    commands.push(Command::new(&format!("push local {}", base + command.get_arg2().unwrap())));
+                },
+                (CommandType::Pop, segment @ ("argument" | "local")) => {
+                    let base = if segment == "argument" { arguments_base } else { locals_base };
+                    commands.push(Command::new(&format!("pop local {}", base + command.get_arg2().unwrap())));
+                },
+                (CommandType::Label, label) => commands.push(Command::new(&format!("label {}${}", label_prefix, label))),
+                (CommandType::Goto, label) => commands.push(Command::new(&format!("goto {}${}", label_prefix, label))),
+                (CommandType::If, label) => commands.push(Command::new(&format!("if-goto {}${}", label_prefix, label))),
+                (CommandType::Return, _) if index == last_index => {},
+                (CommandType::Return, _) => {
+                    needs_end_label = true;
+                    commands.push(Command::new(&format!("goto {}", end_label)));
+                },
+                _ => commands.push(command.clone()),
+            }
+        }
+
+        if needs_end_label {
+            commands.push(Command::new(&format!("label {}", end_label)));
+        }
+        for (offset, pointer) in candidate.written_pointers.iter().enumerate() {
+            commands.push(Command::new(&format!("push local {}", saved_base + offset as i32)));
+            commands.push(Command::new(&format!("pop pointer {}", pointer)));
+        }
+
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::{Command, CommandType}, program::{Program, VmFile}};
+
+    use super::inline_functions;
+
+    fn strip_line_numbers(commands: &[Command]) -> Vec<(CommandType, String, Option<i32>)> {
+        commands.iter()
+            .map(|command| (
+                command.get_command_type().clone(),
+                command.get_arg1().to_string(),
+                command.get_arg2(),
+            ))
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_functions() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 1\npush local 0\ncall Point.getX 1\nreturn"),
+            ("Point", "function Point.getX 0\npush argument 0\npop pointer 0\npush this 0\nreturn"),
+        ]);
+        let inlined_calls = inline_functions(&mut program, 4);
+        let expected = VmFile::new("Main", "function Main.main 3
+            push local 0
+            pop local 1
+            push pointer 0
+            pop local 2
+            push local 1
+            pop pointer 0
+            push this 0
+            push local 2
+            pop pointer 0
+            return");
+
+        assert_eq!(inlined_calls.len(), 1);
+        assert_eq!(inlined_calls[0].callee, "Point.getX");
+        assert_eq!(strip_line_numbers(&program.files[0].commands), strip_line_numbers(&expected.commands));
+    }
+
+    #[test]
+    fn test_inline_functions_given_labels_and_returns() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 0\npush constant 1\ncall Main.sign 1\nreturn
+                function Main.sign 0\npush argument 0\nif-goto POSITIVE\npush constant 0\nreturn
+                label POSITIVE\npush constant 1\nreturn"),
+        ]);
+        inline_functions(&mut program, 7);
+        let expected = VmFile::new("Main", "function Main.main 1
+            push constant 1
+            pop local 0
+            push local 0
+            if-goto Main.sign$inline.1$POSITIVE
+            push constant 0
+            goto Main.sign$inline.1$END
+            label Main.sign$inline.1$POSITIVE
+            push constant 1
+            label Main.sign$inline.1$END
+            return");
+
+        assert_eq!(
+            strip_line_numbers(program.files[0].functions()[0].commands),
+            strip_line_numbers(&expected.commands));
+    }
+
+    #[test]
+    fn test_inline_functions_given_large_function() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Main.one 0\nreturn\nfunction Main.one 0\npush constant 1\nreturn"),
+        ]);
+
+        assert!(inline_functions(&mut program, 1).is_empty());
+        assert_eq!(inline_functions(&mut program, 2).len(), 1);
+    }
+
+    #[test]
+    fn test_inline_functions_given_recursive_function() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Main.loop 0\nreturn\nfunction Main.loop 0\ncall Main.loop 0\nreturn"),
+        ]);
+
+        assert!(inline_functions(&mut program, 10).is_empty());
+    }
+
+    #[test]
+    fn test_inline_functions_given_static_in_other_file() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Counter.get 0\nreturn"),
+            ("Counter", "function Counter.get 0\npush static 0\nreturn\nfunction Counter.next 0\ncall Counter.get 0\nreturn"),
+        ]);
+        let inlined_calls = inline_functions(&mut program, 10);
+
+        assert_eq!(inlined_calls.len(), 1);
+        assert_eq!(inlined_calls[0].caller, "Counter.next");
+    }
+
+    #[test]
+    fn test_inline_functions_given_unbalanced_return() {
+        let mut program = Program::new(&[
+            ("Main", "function Main.main 0\ncall Main.two 0\nreturn\nfunction Main.two 0\npush constant 1\npush constant 2\nreturn"),
+        ]);
+
+        assert!(inline_functions(&mut program, 10).is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod compact_frame;
+pub mod dead_code;
+pub mod inline;
@@ -1,3 +1,9 @@
+use std::fmt;
+
+use crate::util::find_closest;
+
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum CommandType {
     Arithmetic,
     Push,
@@ -10,82 +16,158 @@ pub enum CommandType {
     Call,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct Command {
     command_type: CommandType,
     arg1: String,
     arg2: Option<i32>,
+    line_number: usize,
+    comments: Vec<(usize, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub column: usize,
+    pub length: usize,
+    pub message: String,
+    pub suggestion: Option<&'static str>,
+}
+
+const ARITHMETIC_COMMANDS: [&str; 9] = ["add", "sub", "neg", "eq", "gt", "lt", "and", "or", "not"];
+const COMMANDS: [&str; 17] = [
+    "push", "pop", "label", "goto", "if-goto", "function", "call", "return",
+    "add", "sub", "neg", "eq", "gt", "lt", "and", "or", "not",
+];
+const SEGMENTS: [&str; 8] = ["argument", "local", "static", "constant", "this", "that", "pointer", "temp"];
+
+impl ParseError {
+    fn new(column: usize, length: usize, message: String) -> ParseError {
+        ParseError { line_number: 0, column, length, message, suggestion: None }
+    }
+
+    fn with_suggestion(mut self, word: &str, candidates: &[&'static str]) -> ParseError {
+        self.suggestion = find_closest(word, candidates);
+        self
+    }
+
+    pub fn with_position(mut self, line_number: usize, column_offset: usize) -> ParseError {
+        self.line_number = line_number;
+        self.column += column_offset;
+        self
+    }
 }
 
 impl Command {
     pub fn new(text: &str) -> Command {
-        match text.split(" ").collect::<Vec<&str>>().as_slice() {
-            ["push", arg1, arg2] => {
-                return Command {
-                    command_type: CommandType::Push,
-                    arg1: arg1.to_string(),
-                    arg2: arg2.parse().ok(),
-                }
-            },
-            ["pop", arg1, arg2] => {
-                return Command {
-                    command_type: CommandType::Pop,
-                    arg1: arg1.to_string(),
-                    arg2: arg2.parse().ok(),
-                }
-            },
-            ["label", label] => {
-                return Command {
-                    command_type: CommandType::Label,
-                    arg1: label.to_string(),
-                    arg2: None,
-                }
-            },
-            ["goto", label] => {
-                return Command {
-                    command_type: CommandType::Goto,
-                    arg1: label.to_string(),
-                    arg2: None,
-                }
-            },
-            ["if-goto", label] => {
-                return Command {
-                    command_type: CommandType::If,
-                    arg1: label.to_string(),
-                    arg2: None,
-                }
-            },
-            ["function", function_name, nvars] => {
-                return Command {
-                    command_type: CommandType::Function,
-                    arg1: function_name.to_string(),
-                    arg2: nvars.parse().ok(),
-                }
-            },
-            ["call", function_name, nvars] => {
-                return Command {
-                    command_type: CommandType::Call,
-                    arg1: function_name.to_string(),
-                    arg2: nvars.parse().ok(),
-                }
-            },
-            ["return"] => {
-                return Command {
-                    command_type: CommandType::Return,
-                    arg1: String::from(""),
-                    arg2: None,
+        match Command::parse(text) {
+            Ok(command) => command,
+            Err(error) => panic!("Invalid command '{}': {}", text, error.message),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Command, ParseError> {
+        let tokens = Command::tokenize(text);
+        let Some(&(column, name)) = tokens.first() else {
+            return Err(ParseError::new(1, 1, String::from("expected a command")));
+        };
+
+        let (command_type, arg_count) = match name {
+            "push" => (CommandType::Push, 2),
+            "pop" => (CommandType::Pop, 2),
+            "label" => (CommandType::Label, 1),
+            "goto" => (CommandType::Goto, 1),
+            "if-goto" => (CommandType::If, 1),
+            "function" => (CommandType::Function, 2),
+            "call" => (CommandType::Call, 2),
+            "return" => (CommandType::Return, 0),
+            _ if ARITHMETIC_COMMANDS.contains(&name) => (CommandType::Arithmetic, 0),
+            _ => return Err(ParseError::new(column, name.len(), format!("unknown command '{}'", name))
+                .with_suggestion(name, &COMMANDS)),
+        };
+
+        if tokens.len() != arg_count + 1 {
+            let (column, length) = match tokens.get(arg_count + 1) {
+                Some(&(column, token)) => (column, token.len()),
+                None => (text.trim_end().len() + 1, 1),
+            };
+            return Err(ParseError::new(column, length, format!(
+                "'{}' expects {} argument(s) but {} were given", name, arg_count, tokens.len() - 1)));
+        }
+
+        if let [_, (column, segment), _] = tokens.as_slice() {
+            if command_type == CommandType::Push || command_type == CommandType::Pop {
+                if !SEGMENTS.contains(segment) {
+                    return Err(ParseError::new(*column, segment.len(), format!("unknown segment '{}'", segment))
+                        .with_suggestion(segment, &SEGMENTS));
                 }
-            },
-            [command] => {
-                return Command {
-                    command_type: CommandType::Arithmetic,
-                    arg1: command.to_string(),
-                    arg2: None,
+                if command_type == CommandType::Pop && *segment == "constant" {
+                    return Err(ParseError::new(*column, segment.len(), String::from("cannot pop to 'constant'")));
                 }
             }
-            _ => {
-                panic!("Invalid command!!");
+        }
+
+        let arg2 = match tokens.get(2) {
+            Some(&(column, token)) => match token.parse::<i32>() {
+                Ok(value) if value >= 0 => Some(value),
+                _ => return Err(ParseError::new(column, token.len(), format!(
+                    "expected a non-negative integer but found '{}'", token))),
+            },
+            None => None,
+        };
+        let arg1 = match command_type {
+            CommandType::Arithmetic => name,
+            CommandType::Return => "",
+            _ => tokens[1].1,
+        };
+
+        Ok(Command {
+            command_type,
+            arg1: arg1.to_string(),
+            arg2,
+            line_number: 0,
+            comments: Vec::new(),
+        })
+    }
+
+    pub fn tokenize(text: &str) -> Vec<(usize, &str)> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(index),
+                (Some(token_start), true) => {
+                    tokens.push((token_start + 1, &text[token_start..index]));
+                    start = None;
+                },
+                _ => {},
             }
         }
+        tokens
+    }
+
+    pub fn with_line_number(mut self, line_number: usize) -> Command {
+        self.line_number = line_number;
+        self
+    }
+
+    pub fn with_comments(mut self, comments: Vec<(usize, String)>) -> Command {
+        self.comments = comments;
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self.command_type {
+            CommandType::Arithmetic => &self.arg1,
+            CommandType::Push => "push",
+            CommandType::Pop => "pop",
+            CommandType::Label => "label",
+            CommandType::Goto => "goto",
+            CommandType::If => "if-goto",
+            CommandType::Function => "function",
+            CommandType::Return => "return",
+            CommandType::Call => "call",
+        }
     }
 
     pub fn get_command_type(&self) -> &CommandType {
@@ -99,4 +181,89 @@ impl Command {
     pub fn get_arg2(&self) -> Option<i32> {
         self.arg2
     }
+
+    pub fn get_line_number(&self) -> usize {
+        self.line_number
+    }
+
+    pub fn get_comments(&self) -> &[(usize, String)] {
+        &self.comments
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.command_type, self.arg2) {
+            (CommandType::Arithmetic | CommandType::Return, _) => write!(f, "{}", self.get_name()),
+            (_, Some(arg2)) => write!(f, "{} {} {}", self.get_name(), self.arg1, arg2),
+            (_, None) => write!(f, "{} {}", self.get_name(), self.arg1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> (usize, usize, String) {
+        let error = Command::parse(text).unwrap_err();
+        (error.column, error.length, error.message)
+    }
+
+    #[test]
+    fn test_parse() {
+        let command = Command::parse("push  local\t2").unwrap();
+        assert_eq!(command.get_command_type(), &CommandType::Push);
+        assert_eq!(command.get_arg1(), "local");
+        assert_eq!(command.get_arg2(), Some(2));
+
+        let command = Command::parse("return").unwrap();
+        assert_eq!(command.get_command_type(), &CommandType::Return);
+        assert_eq!(command.get_arg2(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        for text in ["push local 2", "pop that 0", "add", "not", "label LOOP", "goto LOOP", "if-goto END",
+            "function Main.main 3", "call Math.multiply 2", "return"] {
+            assert_eq!(Command::new(text).to_string(), text);
+        }
+        assert_eq!(Command::new("push  local\t2").to_string(), "push local 2");
+    }
+
+    #[test]
+    fn test_parse_given_unknown_command() {
+        assert_eq!(parse_error("pus constant 1"), (1, 3, String::from("unknown command 'pus'")));
+        assert_eq!(parse_error("addd"), (1, 4, String::from("unknown command 'addd'")));
+    }
+
+    #[test]
+    fn test_parse_given_misspelling() {
+        assert_eq!(Command::parse("pus constant 1").unwrap_err().suggestion, Some("push"));
+        assert_eq!(Command::parse("iff-goto END").unwrap_err().suggestion, Some("if-goto"));
+        assert_eq!(Command::parse("push arg 0").unwrap_err().suggestion, None);
+        assert_eq!(Command::parse("push argumnt 0").unwrap_err().suggestion, Some("argument"));
+    }
+
+    #[test]
+    fn test_parse_given_wrong_argument_count() {
+        assert_eq!(parse_error("push constant"),
+            (14, 1, String::from("'push' expects 2 argument(s) but 1 were given")));
+        assert_eq!(parse_error("add 1"),
+            (5, 1, String::from("'add' expects 0 argument(s) but 1 were given")));
+    }
+
+    #[test]
+    fn test_parse_given_invalid_segment() {
+        assert_eq!(parse_error("push locl 0"), (6, 4, String::from("unknown segment 'locl'")));
+        assert_eq!(parse_error("pop constant 0"), (5, 8, String::from("cannot pop to 'constant'")));
+    }
+
+    #[test]
+    fn test_parse_given_invalid_index() {
+        assert_eq!(parse_error("push local x"),
+            (12, 1, String::from("expected a non-negative integer but found 'x'")));
+        assert_eq!(parse_error("call Main.main -1"),
+            (16, 2, String::from("expected a non-negative integer but found '-1'")));
+    }
 }
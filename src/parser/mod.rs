@@ -1,60 +1,89 @@
 pub mod command;
-pub use command::{Command, CommandType};
+pub use command::{Command, CommandType, ParseError};
 
 
+struct SourceLine {
+    line_number: usize,
+    indent: usize,
+    text: String,
+    comments: Vec<(usize, String)>,
+}
+
 pub struct Parser {
-    lines: Vec<String>,
+    lines: Vec<SourceLine>,
+    remaining_comments: Vec<(usize, String)>,
     current_line_number: i32,
-    current_command: Command,
+    current_command: Option<Command>,
 }
 
 impl Parser {
     pub fn new(file_text: &str) -> Parser {
+        let (lines, remaining_comments) = Parser::get_valid_lines(file_text);
         Parser {
-            lines: Parser::get_valid_lines(file_text),
+            lines,
+            remaining_comments,
             current_line_number: -1,
-            current_command: Command::new(""),
+            current_command: None,
         }
     }
 
-    fn get_valid_lines(file_text: &str) -> Vec<String> {
-        file_text
-            .lines()
-            .map(|line| Parser::get_valid_text(line))
-            .filter(|line| !line.is_empty())
-            .collect()
-    }
+    fn get_valid_lines(file_text: &str) -> (Vec<SourceLine>, Vec<(usize, String)>) {
+        let mut lines = Vec::new();
+        let mut comments = Vec::new();
+
+        for (index, line) in file_text.lines().enumerate() {
+            let (text, comment) = Parser::split_comment(line);
+            comments.extend(comment.map(|comment| (index + 1, comment)));
+            if !text.is_empty() {
+                lines.push(SourceLine {
+                    line_number: index + 1,
+                    indent: line.len() - line.trim_start().len(),
+                    text,
+                    comments: std::mem::take(&mut comments),
+                });
+            }
+        }
 
-    fn get_valid_text(text: &str) -> String {
-        let valid_text: &str = match text.split("//").next() {
-            Some(first_part) => first_part,
-            None => text
-        };
+        (lines, comments)
+    }
 
-        valid_text.trim().to_string()
+    fn split_comment(text: &str) -> (String, Option<String>) {
+        match text.split_once("//") {
+            Some((valid_text, comment)) => (valid_text.trim().to_string(), Some(comment.trim_end().to_string())),
+            None => (text.trim().to_string(), None),
+        }
     }
 
     pub fn has_more_lines(&self) -> bool {
         self.current_line_number < self.lines.len() as i32 -1
     }
 
-    pub fn advance(&mut self) {
-        self.current_line_number += 1;
-        self.current_command = Command::new(&self.lines[self.current_line_number as usize]);
+    pub fn tokens(&self) -> Vec<(usize, usize, &str)> {
+        self.lines.iter()
+            .flat_map(|line| Command::tokenize(&line.text).into_iter()
+                .map(move |(column, token)| (line.line_number, column + line.indent, token)))
+            .collect()
     }
 
-    pub fn command_type(&self) -> &CommandType {
-        self.current_command.get_command_type()
+    pub fn advance(&mut self) -> Result<(), ParseError> {
+        self.current_line_number += 1;
+        let line = &self.lines[self.current_line_number as usize];
+        let command = Command::parse(&line.text)
+            .map_err(|error| error.with_position(line.line_number, line.indent))?;
+        self.current_command = Some(command
+            .with_line_number(line.line_number)
+            .with_comments(line.comments.clone()));
+        Ok(())
     }
 
-    pub fn arg1(&self) -> &str {
-        self.current_command.get_arg1()
+    pub fn remaining_comments(&self) -> &[(usize, String)] {
+        &self.remaining_comments
     }
 
-    pub fn arg2(&self) -> i32 {
-        match self.current_command.get_arg2() {
-            Some(arg2) => arg2,
-            None => panic!("Not available")
+    pub fn command(&self) -> &Command {
+        match &self.current_command {
+            Some(command) => command,
+            None => panic!("No command parsed"),
         }
     }
 }
@@ -62,8 +91,6 @@ impl Parser {
 
 #[cfg(test)]
 mod tests {
-    use std::panic;
-
     use super::*;
 
     #[test]
@@ -97,7 +124,7 @@ mod tests {
     fn test_advance() {
         let mut parser = Parser::new("add");
         assert!(parser.has_more_lines());
-        parser.advance();
+        parser.advance().unwrap();
         assert!(!parser.has_more_lines());
     }
 
@@ -105,10 +132,47 @@ mod tests {
     fn test_advance_given_two_lines() {
         let mut parser = Parser::new("add\nsub");
         assert!(parser.has_more_lines());
-        parser.advance();
+        parser.advance().unwrap();
         assert!(parser.has_more_lines());
-        parser.advance();
+        parser.advance().unwrap();
+        assert!(!parser.has_more_lines());
+    }
+
+    #[test]
+    fn test_line_number() {
+        let mut parser = Parser::new("// comment\npush constant 1\n\n  add // sum\n");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_line_number(), 2);
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_line_number(), 4);
+    }
+
+    #[test]
+    fn test_tokens() {
+        let parser = Parser::new("// comment\npush constant 1\n\n  add // sum\n");
+        assert_eq!(parser.tokens(), vec![(2, 1, "push"), (2, 6, "constant"), (2, 15, "1"), (4, 3, "add")]);
+    }
+
+    #[test]
+    fn test_comments() {
+        let mut parser = Parser::new("// Main.main\n//\n// let x = 1\npush constant 1 // 1\npop local 0\n// end");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_comments(), [
+            (1, String::from(" Main.main")), (2, String::new()), (3, String::from(" let x = 1")), (4, String::from(" 1")),
+        ]);
+        parser.advance().unwrap();
+        assert!(parser.command().get_comments().is_empty());
         assert!(!parser.has_more_lines());
+        assert_eq!(parser.remaining_comments(), [(6, String::from(" end"))]);
+    }
+
+    #[test]
+    fn test_advance_given_invalid_command() {
+        let mut parser = Parser::new("push constant 1\n\n    pop locl 0");
+        parser.advance().unwrap();
+        let error = parser.advance().unwrap_err();
+        assert_eq!((error.line_number, error.column, error.length), (3, 9, 4));
+        assert_eq!(error.message, "unknown segment 'locl'");
     }
 
     #[test]
@@ -118,126 +182,126 @@ mod tests {
         ];
         for command in commands {
             let mut parser = Parser::new(command);
-            parser.advance();
-            assert!(matches!(parser.command_type(), CommandType::Arithmetic));
+            parser.advance().unwrap();
+            assert!(matches!(parser.command().get_command_type(), CommandType::Arithmetic));
         }
     }
 
     #[test]
     fn test_command_type_given_stack_command() {
         let mut parser = Parser::new("push constant 17\npop local 2");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Push));
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Pop));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Push));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Pop));
     }
 
     #[test]
     fn test_command_type_given_label_command() {
         let mut parser = Parser::new("label LABEL");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Label));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Label));
     }
 
     #[test]
     fn test_command_type_given_goto_command() {
         let mut parser = Parser::new("goto LABEL");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Goto));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Goto));
     }
 
     #[test]
     fn test_command_type_given_if_command() {
         let mut parser = Parser::new("if-goto LABEL");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::If));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::If));
     }
 
     #[test]
     fn test_command_type_given_function_command() {
         let mut parser = Parser::new("function FUNC 0");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Function));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Function));
     }
 
     #[test]
     fn test_command_type_given_call_command() {
         let mut parser = Parser::new("call FUNC 0");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Call));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Call));
     }
 
     #[test]
     fn test_command_type_given_return_command() {
         let mut parser = Parser::new("return");
-        parser.advance();
-        assert!(matches!(parser.command_type(), CommandType::Return));
+        parser.advance().unwrap();
+        assert!(matches!(parser.command().get_command_type(), CommandType::Return));
     }
 
     #[test]
     fn test_arg_given_arithmetic_command() {
         let mut parser = Parser::new("add");
-        parser.advance();
-        assert_eq!(parser.arg1(), "add");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "add");
     }
 
     #[test]
     fn test_arg_given_push_command() {
         let mut parser = Parser::new("push constant 1");
-        parser.advance();
-        assert_eq!(parser.arg1(), "constant");
-        assert_eq!(parser.arg2(), 1);
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "constant");
+        assert_eq!(parser.command().get_arg2(), Some(1));
     }
 
     #[test]
     fn test_arg_given_pop_command() {
         let mut parser = Parser::new("pop temp 12");
-        parser.advance();
-        assert_eq!(parser.arg1(), "temp");
-        assert_eq!(parser.arg2(), 12);
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "temp");
+        assert_eq!(parser.command().get_arg2(), Some(12));
     }
 
     #[test]
     fn test_arg_given_invalid_type() {
         let mut parser = Parser::new("sub");
-        parser.advance();
-        assert!(panic::catch_unwind(|| { parser.arg2(); }).is_err());
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg2(), None);
     }
 
     #[test]
     fn test_arg_given_label_command() {
         let mut parser = Parser::new("label LABEL");
-        parser.advance();
-        assert_eq!(parser.arg1(), "LABEL");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "LABEL");
     }
 
     #[test]
     fn test_arg_given_goto_command() {
         let mut parser = Parser::new("goto LABEL");
-        parser.advance();
-        assert_eq!(parser.arg1(), "LABEL");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "LABEL");
     }
 
     #[test]
     fn test_arg_given_if_command() {
         let mut parser = Parser::new("if-goto LABEL");
-        parser.advance();
-        assert_eq!(parser.arg1(), "LABEL");
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "LABEL");
     }
 
     #[test]
     fn test_arg_given_function_command() {
         let mut parser = Parser::new("function FUNC 0");
-        parser.advance();
-        assert_eq!(parser.arg1(), "FUNC");
-        assert_eq!(parser.arg2(), 0);
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "FUNC");
+        assert_eq!(parser.command().get_arg2(), Some(0));
     }
 
     #[test]
     fn test_arg_given_call_command() {
         let mut parser = Parser::new("call FUNC 0");
-        parser.advance();
-        assert_eq!(parser.arg1(), "FUNC");
-        assert_eq!(parser.arg2(), 0);
+        parser.advance().unwrap();
+        assert_eq!(parser.command().get_arg1(), "FUNC");
+        assert_eq!(parser.command().get_arg2(), Some(0));
     }
 }
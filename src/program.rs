@@ -0,0 +1,235 @@
+use std::path::Path;
+
+use glob::glob;
+
+use crate::{
+    diagnostics::{registry::INVALID_COMMAND, Diagnostic}, json::Json,
+    parser::{Command, CommandType, Parser}, util::load_text,
+};
+
+
+pub struct VmFile {
+    pub name: String,
+    pub path: String,
+    pub commands: Vec<Command>,
+    pub remaining_comments: Vec<(usize, String)>,
+}
+
+pub struct Function<'a> {
+    pub name: &'a str,
+    pub commands: &'a [Command],
+}
+
+pub struct Program {
+    pub files: Vec<VmFile>,
+}
+
+impl VmFile {
+    #[cfg(test)]
+    pub fn new(name: &str, file_text: &str) -> VmFile {
+        match VmFile::parse(name, &format!("{}.vm", name), file_text) {
+            Ok(vm_file) => vm_file,
+            Err(diagnostics) => panic!("{}", diagnostics[0]),
+        }
+    }
+
+    pub fn parse(name: &str, path: &str, file_text: &str) -> Result<VmFile, Vec<Diagnostic>> {
+        let mut parser = Parser::new(file_text);
+        let mut commands = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        while parser.has_more_lines() {
+            match parser.advance() {
+                Ok(()) => commands.push(parser.command().clone()),
+                Err(error) => {
+                    let source_line = file_text.lines().nth(error.line_number - 1).unwrap_or_default();
+                    let mut diagnostic = Diagnostic::new(&INVALID_COMMAND, path, error.line_number, error.message)
+                        .with_column(error.column)
+                        .with_snippet(source_line, error.length);
+                    if let Some(suggestion) = error.suggestion {
+                        diagnostic = diagnostic.with_help(format!("did you mean `{}`?", suggestion));
+                    }
+                    diagnostics.push(diagnostic);
+                },
+            }
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+        Ok(VmFile {
+            name: name.to_string(),
+            path: path.to_string(),
+            commands,
+            remaining_comments: parser.remaining_comments().to_vec(),
+        })
+    }
+
+    pub fn load(file_path: &Path) -> Result<VmFile, Vec<Diagnostic>> {
+        let name = file_path.file_stem().unwrap().to_string_lossy().to_string();
+        VmFile::parse(&name, &file_path.to_string_lossy(), &load_text(file_path))
+    }
+
+    pub fn top_level_commands(&self) -> &[Command] {
+        let end = self.commands.iter()
+            .position(|command| command.get_command_type() == &CommandType::Function)
+            .unwrap_or(self.commands.len());
+        &self.commands[..end]
+    }
+
+    pub fn functions(&self) -> Vec<Function<'_>> {
+        let starts: Vec<usize> = self.commands.iter()
+            .enumerate()
+            .filter(|(_, command)| command.get_command_type() == &CommandType::Function)
+            .map(|(index, _)| index)
+            .collect();
+
+        starts.iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = starts.get(i + 1).copied().unwrap_or(self.commands.len());
+                Function {
+                    name: self.commands[start].get_arg1(),
+                    commands: &self.commands[start..end],
+                }
+            })
+            .collect()
+    }
+}
+
+impl Program {
+    #[cfg(test)]
+    pub fn new(files: &[(&str, &str)]) -> Program {
+        Program {
+            files: files.iter().map(|(name, text)| VmFile::new(name, text)).collect(),
+        }
+    }
+
+    pub fn load_folder(input_folder: &Path) -> Result<Program, Vec<Diagnostic>> {
+        let pattern = input_folder.join("*.vm").to_string_lossy().to_string();
+        let mut files = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for vm_file in glob(&pattern).unwrap() {
+            match VmFile::load(vm_file.unwrap().as_path()) {
+                Ok(vm_file) => files.push(vm_file),
+                Err(file_diagnostics) => diagnostics.extend(file_diagnostics),
+            }
+        }
+
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+        Ok(Program { files })
+    }
+}
+
+pub fn to_json(program: &Program) -> Json {
+    let files = program.files.iter()
+        .map(|file| Json::object(vec![
+            ("file", Json::from(format!("{}.vm", file.name).as_str())),
+            ("commands", Json::Array(file.commands.iter()
+                .map(|command| Json::object(vec![
+                    ("line", Json::from(command.get_line_number())),
+                    ("command", Json::from(command.get_name())),
+                    ("arg1", match command.get_command_type() {
+                        CommandType::Arithmetic | CommandType::Return => Json::Null,
+                        _ => Json::from(command.get_arg1()),
+                    }),
+                    ("arg2", command.get_arg2().map_or(Json::Null, |arg2| Json::Number(arg2 as i64))),
+                ]))
+                .collect())),
+        ]))
+        .collect();
+
+    Json::object(vec![("files", Json::Array(files))])
+}
+
+pub fn to_vm_text(program: &Program) -> String {
+    program.files.iter()
+        .map(|file| {
+            let commands: String = file.commands.iter().map(|command| format!("{}\n", command)).collect();
+            format!("// {}.vm\n{}", file.name, commands)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_functions_given_top_level_commands() {
+        let file = VmFile::new("Main", "push constant 1\nfunction Main.a 0\nreturn\nfunction Main.b 1\npush constant 2\nreturn");
+
+        assert_eq!(file.top_level_commands().len(), 1);
+
+        let functions = file.functions();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "Main.a");
+        assert_eq!(functions[0].commands.len(), 2);
+        assert_eq!(functions[1].name, "Main.b");
+        assert_eq!(functions[1].commands.len(), 3);
+    }
+
+    #[test]
+    fn test_functions_given_no_function() {
+        let file = VmFile::new("Main", "push constant 1\nadd");
+
+        assert_eq!(file.top_level_commands().len(), 2);
+        assert!(file.functions().is_empty());
+    }
+
+    #[test]
+    fn test_parse_given_invalid_commands() {
+        let Err(diagnostics) = VmFile::parse("Main", "Main.vm", "push constant 1\n  pop constnt 0\nadd 1") else {
+            panic!("Expected parse errors");
+        };
+        let positions: Vec<(usize, usize)> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.line_number, diagnostic.column))
+            .collect();
+
+        assert_eq!(positions, vec![(2, 7), (3, 5)]);
+        assert_eq!(diagnostics[0].message, "unknown segment 'constnt'");
+        assert_eq!(diagnostics[0].help, Some(String::from("did you mean `constant`?")));
+        assert_eq!(diagnostics[1].snippet.as_ref().unwrap().source_line, "add 1");
+    }
+
+    #[test]
+    fn test_load_folder() {
+        let program = Program::load_folder(Path::new("test_data/TestFolder")).unwrap();
+        let names: Vec<&str> = program.files.iter().map(|file| file.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Main", "Math"]);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let program = Program::new(&[("Main", "function Main.main 0\n\npush constant 1\nreturn")]);
+
+        assert_eq!(to_json(&program).to_string(), concat!(
+            r#"{"files":[{"file":"Main.vm","commands":["#,
+            r#"{"line":1,"command":"function","arg1":"Main.main","arg2":0},"#,
+            r#"{"line":3,"command":"push","arg1":"constant","arg2":1},"#,
+            r#"{"line":4,"command":"return","arg1":null,"arg2":null}]}]}"#));
+    }
+
+    #[test]
+    fn test_to_vm_text() {
+        let program = Program::new(&[
+            ("Main", "function  Main.main 0\n  call Math.one 0 // one\nreturn"),
+            ("Math", "function Math.one 0\npush constant 1\nreturn"),
+        ]);
+
+        assert_eq!(to_vm_text(&program), "\
+// Main.vm
+function Main.main 0
+call Math.one 0
+return
+// Math.vm
+function Math.one 0
+push constant 1
+return
+");
+    }
+}
@@ -0,0 +1,71 @@
+use crate::json::Json;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceMapping {
+    pub vm_file: String,
+    pub line_number: usize,
+    pub function_name: String,
+    pub asm_start_line: usize,
+    pub asm_end_line: usize,
+    pub rom_start: usize,
+    pub rom_end: usize,
+}
+
+pub fn to_json(asm_file: &str, mappings: &[SourceMapping]) -> Json {
+    let mappings = mappings.iter()
+        .map(|mapping| Json::object(vec![
+            ("asmLines", Json::Array(vec![
+                Json::from(mapping.asm_start_line), Json::from(mapping.asm_end_line),
+            ])),
+            ("rom", Json::Array(vec![Json::from(mapping.rom_start), Json::from(mapping.rom_end)])),
+            ("source", Json::from(mapping.vm_file.as_str())),
+            ("line", Json::from(mapping.line_number)),
+            ("function", if mapping.function_name.is_empty() {
+                Json::Null
+            } else {
+                Json::from(mapping.function_name.as_str())
+            }),
+        ]))
+        .collect();
+
+    Json::object(vec![
+        ("version", Json::from(1)),
+        ("file", Json::from(asm_file)),
+        ("mappings", Json::Array(mappings)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_json, SourceMapping};
+
+    #[test]
+    fn test_to_json() {
+        let mappings = vec![
+            SourceMapping {
+                vm_file: String::from("Main.vm"),
+                line_number: 1,
+                function_name: String::from("Main.main"),
+                asm_start_line: 2,
+                asm_end_line: 3,
+                rom_start: 0,
+                rom_end: 0,
+            },
+            SourceMapping {
+                vm_file: String::from("Main.vm"),
+                line_number: 2,
+                function_name: String::new(),
+                asm_start_line: 4,
+                asm_end_line: 10,
+                rom_start: 0,
+                rom_end: 6,
+            },
+        ];
+
+        assert_eq!(to_json("Main.asm", &mappings).to_string(), concat!(
+            r#"{"version":1,"file":"Main.asm","mappings":["#,
+            r#"{"asmLines":[2,3],"rom":[0,0],"source":"Main.vm","line":1,"function":"Main.main"},"#,
+            r#"{"asmLines":[4,10],"rom":[0,6],"source":"Main.vm","line":2,"function":null}]}"#));
+    }
+}
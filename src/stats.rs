@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use crate::code_writer::CommandStat;
+
+
+const KINDS: [(&str, &str); 11] = [
+    ("push", "push"), ("pop", "pop"), ("arithmetic", "arith"), ("comparison", "cmp"),
+    ("label", "label"), ("goto", "goto"), ("if-goto", "if"), ("function", "func"), ("call", "call"),
+    ("return", "ret"), ("tail call", "tail"),
+];
+const TOTAL_KINDS: [(&str, &str); 4] = [
+    ("call", "calls"), ("return", "returns"), ("tail call", "tail calls"), ("comparison", "comparisons"),
+];
+
+type FileUsage<'a> = (Usage, HashMap<&'a str, Usage>);
+
+#[derive(Default)]
+struct Usage {
+    instruction_count: usize,
+    command_counts: HashMap<&'static str, usize>,
+}
+
+impl Usage {
+    fn add(&mut self, command_stat: &CommandStat) {
+        self.instruction_count += command_stat.instruction_count;
+        *self.command_counts.entry(command_stat.kind).or_default() += 1;
+    }
+
+    fn format_row(&self, name: &str) -> String {
+        let vm_count: usize = self.command_counts.values().sum();
+        let mut row = format!("{:<32}{:>7}{:>6}", name, self.instruction_count, vm_count);
+        for (kind, _) in KINDS {
+            row.push_str(&format!("{:>6}", self.command_counts.get(kind).copied().unwrap_or_default()));
+        }
+        row
+    }
+}
+
+pub fn get_stats_text(command_stats: &[CommandStat], rom_size: usize) -> String {
+    let mut files: HashMap<&str, FileUsage> = HashMap::new();
+    let mut totals: HashMap<&str, (usize, usize)> = HashMap::new();
+    for command_stat in command_stats {
+        let (file_usage, functions) = files.entry(&command_stat.vm_file).or_default();
+        file_usage.add(command_stat);
+        let function_name = if command_stat.function_name.is_empty() { "(top level)" } else { &command_stat.function_name };
+        functions.entry(function_name).or_default().add(command_stat);
+
+        let total = totals.entry(command_stat.kind).or_default();
+        total.0 += 1;
+        total.1 += command_stat.instruction_count;
+    }
+
+    let mut header = format!("{:<32}{:>7}{:>6}", "file / function", "hack", "vm");
+    for (_, column) in KINDS {
+        header.push_str(&format!("{:>6}", column));
+    }
+    let mut lines = vec![header];
+
+    let mut files: Vec<(&str, FileUsage)> = files.into_iter().collect();
+    files.sort_by(|a, b| b.1.0.instruction_count.cmp(&a.1.0.instruction_count).then(a.0.cmp(b.0)));
+    for (vm_file, (file_usage, functions)) in files {
+        lines.push(file_usage.format_row(vm_file));
+        let mut functions: Vec<(&str, Usage)> = functions.into_iter().collect();
+        functions.sort_by(|a, b| b.1.instruction_count.cmp(&a.1.instruction_count).then(a.0.cmp(b.0)));
+        for (function_name, usage) in functions {
+            lines.push(usage.format_row(&format!("  {}", function_name)));
+        }
+    }
+
+    let mapped_size: usize = command_stats.iter().map(|command_stat| command_stat.instruction_count).sum();
+    lines.push(format!("{:<32}{:>7}", "(generated)", rom_size - mapped_size));
+    lines.push(format!("{:<32}{:>7}", "total", rom_size));
+    lines.push(String::new());
+
+    for (kind, name) in TOTAL_KINDS {
+        let (count, instruction_count) = totals.get(kind).copied().unwrap_or_default();
+        lines.push(format!("{:<13}{:>6} commands{:>8} instructions", name, count, instruction_count));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code_writer::CommandStat;
+
+    use super::get_stats_text;
+
+    fn new_stat(vm_file: &str, function_name: &str, kind: &'static str, instruction_count: usize) -> CommandStat {
+        CommandStat {
+            vm_file: vm_file.to_string(),
+            function_name: function_name.to_string(),
+            kind,
+            instruction_count,
+        }
+    }
+
+    #[test]
+    fn test_get_stats_text() {
+        let command_stats = vec![
+            new_stat("Main.vm", "Main.main", "function", 0),
+            new_stat("Main.vm", "Main.main", "push", 7),
+            new_stat("Main.vm", "Main.main", "call", 49),
+            new_stat("Main.vm", "Main.main", "return", 48),
+            new_stat("Math.vm", "Math.one", "function", 0),
+            new_stat("Math.vm", "Math.one", "push", 7),
+            new_stat("Math.vm", "Math.one", "push", 7),
+            new_stat("Math.vm", "Math.one", "comparison", 20),
+            new_stat("Math.vm", "Math.one", "tail call", 60),
+        ];
+
+        assert_eq!(get_stats_text(&command_stats, 250), "\
+file / function                    hack    vm  push   pop arith   cmp label  goto    if  func  call   ret  tail
+Main.vm                             104     4     1     0     0     0     0     0     0     1     1     1     0
+  Main.main                         104     4     1     0     0     0     0     0     0     1     1     1     0
+Math.vm                              94     5     2     0     0     1     0     0     0     1     0     0     1
+  Math.one                           94     5     2     0     0     1     0     0     0     1     0     0     1
+(generated)                          52
+total                               250
+
+calls             1 commands      49 instructions
+returns           1 commands      48 instructions
+tail calls        1 commands      60 instructions
+comparisons       1 commands      20 instructions");
+    }
+}
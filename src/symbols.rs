@@ -0,0 +1,103 @@
+use crate::{
+    assembler::SymbolTable, code_writer::{AsmSymbol, SymbolKind}, json::Json,
+};
+
+
+pub struct SymbolEntry<'a> {
+    pub symbol: &'a AsmSymbol,
+    pub memory: &'static str,
+    pub address: u16,
+}
+
+pub fn get_symbol_entries<'a>(symbols: &'a [AsmSymbol], asm_text: &str) -> Vec<SymbolEntry<'a>> {
+    let symbol_table = SymbolTable::new(asm_text);
+    let mut entries: Vec<SymbolEntry> = symbols.iter()
+        .filter_map(|symbol| {
+            let address = symbol_table.get_address(&symbol.name)?;
+            let memory = if symbol.kind == SymbolKind::Static { "RAM" } else { "ROM" };
+            Some(SymbolEntry { symbol, memory, address })
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| (entry.memory, entry.address));
+    entries
+}
+
+pub fn get_symbols_text(entries: &[SymbolEntry]) -> String {
+    let mut lines = vec![String::from("# memory address kind file symbol")];
+    for entry in entries {
+        lines.push(format!("{} {:>5} {:<8} {} {}",
+            entry.memory, entry.address, get_kind_name(entry.symbol.kind),
+            get_file_name(entry.symbol), entry.symbol.name));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn to_json(entries: &[SymbolEntry]) -> Json {
+    let symbols = entries.iter()
+        .map(|entry| Json::object(vec![
+            ("name", Json::from(entry.symbol.name.as_str())),
+            ("kind", Json::from(get_kind_name(entry.symbol.kind))),
+            ("memory", Json::from(entry.memory)),
+            ("address", Json::from(entry.address as usize)),
+            ("file", match &entry.symbol.file_name {
+                Some(file_name) => Json::from(format!("{}.vm", file_name).as_str()),
+                None => Json::Null,
+            }),
+        ]))
+        .collect();
+
+    Json::object(vec![("symbols", Json::Array(symbols))])
+}
+
+fn get_kind_name(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Static => "static",
+        SymbolKind::Function => "function",
+        SymbolKind::Return => "return",
+        SymbolKind::Label => "label",
+        SymbolKind::Branch => "branch",
+    }
+}
+
+fn get_file_name(symbol: &AsmSymbol) -> String {
+    match &symbol.file_name {
+        Some(file_name) => format!("{}.vm", file_name),
+        None => String::from("-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::code_writer::{AsmSymbol, SymbolKind};
+
+    use super::{get_symbol_entries, get_symbols_text, to_json};
+
+    fn new_symbol(name: &str, kind: SymbolKind, file_name: Option<&str>) -> AsmSymbol {
+        AsmSymbol { name: name.to_string(), kind, file_name: file_name.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_get_symbols_text() {
+        let symbols = vec![
+            new_symbol("Boot$ret.1", SymbolKind::Return, None),
+            new_symbol("Main.main", SymbolKind::Function, Some("Main")),
+            new_symbol("Main.3", SymbolKind::Static, Some("Main")),
+            new_symbol("Main.main$LOOP", SymbolKind::Label, Some("Main")),
+        ];
+        let asm_text = "@Main.main\n0;JMP\n(Boot$ret.1)\n(Main.main)\n@Main.3\n(Main.main$LOOP)\n@Main.main$LOOP\n0;JMP";
+        let entries = get_symbol_entries(&symbols, asm_text);
+
+        assert_eq!(get_symbols_text(&entries), "\
+# memory address kind file symbol
+RAM    16 static   Main.vm Main.3
+ROM     2 return   - Boot$ret.1
+ROM     2 function Main.vm Main.main
+ROM     3 label    Main.vm Main.main$LOOP
+");
+        assert!(to_json(&entries).to_string().starts_with(
+            r#"{"symbols":[{"name":"Main.3","kind":"static","memory":"RAM","address":16,"file":"Main.vm"},"#));
+    }
+}
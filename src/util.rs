@@ -7,3 +7,47 @@ pub fn load_text<P: AsRef<Path>>(file_path: P) -> String {
     file.read_to_string(&mut text).unwrap();
     text
 }
+
+pub fn find_closest<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates.iter()
+        .map(|candidate| (get_edit_distance(word, candidate), *candidate))
+        .filter(|(distance, candidate)| *distance <= 2 && *distance < candidate.len().min(word.len()))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(previous + 1);
+        }
+    }
+    distances[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_closest, get_edit_distance};
+
+    #[test]
+    fn test_get_edit_distance() {
+        assert_eq!(get_edit_distance("kitten", "sitting"), 3);
+        assert_eq!(get_edit_distance("", "pop"), 3);
+        assert_eq!(get_edit_distance("local", "local"), 0);
+    }
+
+    #[test]
+    fn test_find_closest() {
+        let candidates = ["argument", "local", "static"];
+        assert_eq!(find_closest("arguments", &candidates), Some("argument"));
+        assert_eq!(find_closest("locl", &candidates), Some("local"));
+        assert_eq!(find_closest("heap", &candidates), None);
+    }
+}
@@ -1,8 +1,6 @@
 // push local 0
   @LCL
-  D=M
-  @0
-  A=D+A
+  A=M
   D=M
   @SP
   A=M
@@ -0,0 +1,81 @@
+// function CompactCall.test 0
+(CompactCall.test)
+// call Math.add 2
+  @CompactCall.test$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @3
+  D=D-A
+  @2
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Math.add
+  0;JMP
+(CompactCall.test$ret.1)
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @3
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
@@ -0,0 +1,69 @@
+// function TailCall.test 0
+(TailCall.test)
+// tail call Math.add 2
+  @LCL
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(TailCall.test_COPY1)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @TailCall.test_COPY1
+  D;JGT
+  @R14
+  D=M
+  @7
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @7
+  D=A
+  @R15
+  M=D
+(TailCall.test_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @TailCall.test_COPY2
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Math.add
+  0;JMP
@@ -0,0 +1,1128 @@
+// bootstrap
+  @256
+  D=A
+  @SP
+  M=D
+// call Sys.init 0
+  @TestCompactFrame$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @0
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Sys.init
+  0;JMP
+(TestCompactFrame$ret.1)
+// > Main.asm
+// function Main.triangle 0
+(Main.triangle)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// tail call Main.sum 2
+  @LCL
+  D=M
+  @3
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @3
+  D=A
+  @R15
+  M=D
+(Main.triangle_COPY1)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.triangle_COPY1
+  D;JGT
+  @R14
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(Main.triangle_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.triangle_COPY2
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Main.sum
+  0;JMP
+// function Main.sum 0
+(Main.sum)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// eq
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @Main.sum_THEN1
+  D;JEQ
+  D=0
+  @Main.sum_END1
+  0;JMP
+(Main.sum_THEN1)
+  D=-1
+(Main.sum_END1)
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// if DONE
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @Main.sum$DONE
+  D;JNE
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 1
+  @1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// sub
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// tail call Main.sum 2
+  @LCL
+  D=M
+  @3
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @3
+  D=A
+  @R15
+  M=D
+(Main.sum_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.sum_COPY2
+  D;JGT
+  @R14
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(Main.sum_COPY3)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.sum_COPY3
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Main.sum
+  0;JMP
+// label DONE
+(Main.sum$DONE)
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @3
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Main.getX 0
+(Main.getX)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// tail call Obj.getX 1
+  @LCL
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(Main.getX_COPY1)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.getX_COPY1
+  D;JGT
+  @R14
+  D=M
+  @6
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @6
+  D=A
+  @R15
+  M=D
+(Main.getX_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.getX_COPY2
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Obj.getX
+  0;JMP
+// > Obj.asm
+// function Obj.new 0
+(Obj.new)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push constant 7
+  @7
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop this 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  A=M
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Obj.getX 0
+(Obj.getX)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push this 0
+  @THIS
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Obj.sumX 0
+(Obj.sumX)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push this 0
+  @THIS
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.sum 2
+  @Obj.sumX$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @3
+  D=D-A
+  @2
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.sum
+  0;JMP
+(Obj.sumX$ret.1)
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// > Sys.asm
+// function Sys.init 0
+(Sys.init)
+// push constant 4000
+  @4000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push constant 5000
+  @5000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THAT
+  M=D
+// push constant 3000
+  @3000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Obj.new 1
+  @Sys.init$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Obj.new
+  0;JMP
+(Sys.init$ret.1)
+// pop temp 0
+  @5
+  D=A
+  @0
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push constant 10
+  @10
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.triangle 1
+  @Sys.init$ret.2
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @3
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.triangle
+  0;JMP
+(Sys.init$ret.2)
+// pop temp 1
+  @5
+  D=A
+  @1
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push constant 3000
+  @3000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.getX 1
+  @Sys.init$ret.3
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.getX
+  0;JMP
+(Sys.init$ret.3)
+// pop temp 2
+  @5
+  D=A
+  @2
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push constant 3000
+  @3000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Obj.sumX 1
+  @Sys.init$ret.4
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Obj.sumX
+  0;JMP
+(Sys.init$ret.4)
+// pop temp 5
+  @5
+  D=A
+  @5
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop temp 3
+  @5
+  D=A
+  @3
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push pointer 1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop temp 4
+  @5
+  D=A
+  @4
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// label HALT
+(Sys.init$HALT)
+// goto HALT
+  @Sys.init$HALT
+  0;JMP
@@ -0,0 +1,409 @@
+// bootstrap
+  @256
+  D=A
+  @SP
+  M=D
+// call Sys.init 0
+  @TestDeadCode$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @0
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Sys.init
+  0;JMP
+(TestDeadCode$ret.1)
+// > Main.asm
+// function Main.main 0
+(Main.main)
+// push constant 2
+  @2
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.double 1
+  @Main.main$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.double
+  0;JMP
+(Main.main$ret.1)
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Main.double 0
+(Main.double)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Main.handler 0
+(Main.handler)
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// > Sys.asm
+// function Sys.init 0
+(Sys.init)
+// call Main.main 0
+  @Sys.init$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @0
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.main
+  0;JMP
+(Sys.init$ret.1)
+// pop temp 0
+  @5
+  D=A
+  @0
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// label HALT
+(Sys.init$HALT)
+// goto HALT
+  @Sys.init$HALT
+  0;JMP
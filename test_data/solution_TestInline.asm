@@ -0,0 +1,816 @@
+// bootstrap
+  @256
+  D=A
+  @SP
+  M=D
+// call Sys.init 0
+  @TestInline$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @0
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Sys.init
+  0;JMP
+(TestInline$ret.1)
+// > Math.asm
+// function Math.multiply 1
+(Math.multiply)
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M
+  M=D
+// label LOOP
+(Math.multiply$LOOP)
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// eq
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @Math.multiply_THEN1
+  D;JEQ
+  D=0
+  @Math.multiply_END1
+  0;JMP
+(Math.multiply_THEN1)
+  D=-1
+(Math.multiply_END1)
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// if END
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @Math.multiply$END
+  D;JNE
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M
+  M=D
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 1
+  @1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// sub
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop argument 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M+1
+  M=D
+// goto LOOP
+  @Math.multiply$LOOP
+  0;JMP
+// label END
+(Math.multiply$END)
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// > Point.asm
+// > Sys.asm
+// function Sys.init 4
+(Sys.init)
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+// push constant 3000
+  @3000
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 2
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  M=D
+// push local 1
+  @LCL
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push constant 5
+  @5
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop this 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  A=M
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// pop local 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M
+  M=D
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 2
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  M=D
+// push local 1
+  @LCL
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push this 0
+  @THIS
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 4
+  @4
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 2
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  M=D
+// pop local 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  M=D
+// push pointer 0
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 3
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  A=A+1
+  M=D
+// push local 1
+  @LCL
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// push this 0
+  @THIS
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Math.multiply 2
+  @Sys.init$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @2
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Math.multiply
+  0;JMP
+(Sys.init$ret.1)
+// push local 3
+  @LCL
+  D=M
+  @3
+  A=D+A
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop pointer 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @THIS
+  M=D
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 7
+  @7
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 1
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  M=D
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 2
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  M=D
+// push local 1
+  @LCL
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 2
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M+1
+  A=A+1
+  M=D
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// lt
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @Sys.init_THEN1
+  D;JLT
+  D=0
+  @Sys.init_END1
+  0;JMP
+(Sys.init_THEN1)
+  D=-1
+(Sys.init_END1)
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// if Sys.abs$inline.4$NEGATIVE
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @Sys.init$Sys.abs$inline.4$NEGATIVE
+  D;JNE
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// goto Sys.abs$inline.4$END
+  @Sys.init$Sys.abs$inline.4$END
+  0;JMP
+// label Sys.abs$inline.4$NEGATIVE
+(Sys.init$Sys.abs$inline.4$NEGATIVE)
+// push local 2
+  @LCL
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// neg
+  @SP
+  M=M-1
+  A=M
+  D=M
+  D=-D
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// label Sys.abs$inline.4$END
+(Sys.init$Sys.abs$inline.4$END)
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop temp 0
+  @5
+  D=A
+  @0
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// label HALT
+(Sys.init$HALT)
+// goto HALT
+  @Sys.init$HALT
+  0;JMP
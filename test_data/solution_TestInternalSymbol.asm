@@ -163,9 +163,7 @@
   M=M+1
 // push argument 0
   @ARG
-  D=M
-  @0
-  A=D+A
+  A=M
   D=M
   @SP
   A=M
@@ -174,9 +172,7 @@
   M=M+1
 // push argument 1
   @ARG
-  D=M
-  @1
-  A=D+A
+  A=M+1
   D=M
   @SP
   A=M
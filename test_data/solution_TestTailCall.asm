@@ -0,0 +1,698 @@
+// bootstrap
+  @256
+  D=A
+  @SP
+  M=D
+// call Sys.init 0
+  @TestTailCall$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @0
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Sys.init
+  0;JMP
+(TestTailCall$ret.1)
+// > Main.asm
+// function Main.count 0
+(Main.count)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// eq
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @Main.count_THEN1
+  D;JEQ
+  D=0
+  @Main.count_END1
+  0;JMP
+(Main.count_THEN1)
+  D=-1
+(Main.count_END1)
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// if DONE
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @Main.count$DONE
+  D;JNE
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 1
+  @1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// sub
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=M-D
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 1
+  @1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// tail call Main.count 2
+  @LCL
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(Main.count_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.count_COPY2
+  D;JGT
+  @R14
+  D=M
+  @7
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @7
+  D=A
+  @R15
+  M=D
+(Main.count_COPY3)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.count_COPY3
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Main.count
+  0;JMP
+// label DONE
+(Main.count$DONE)
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// function Main.twice 1
+(Main.twice)
+  @SP
+  A=M
+  M=0
+  @SP
+  M=M+1
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// pop local 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M
+  M=D
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 100
+  @100
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// tail call Main.sum3 3
+  @LCL
+  D=M
+  @5
+  D=D-A
+  @R13
+  M=D
+  @SP
+  D=M
+  @R14
+  M=D
+  @5
+  D=A
+  @R15
+  M=D
+(Main.twice_COPY1)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.twice_COPY1
+  D;JGT
+  @R14
+  D=M
+  @8
+  D=D-A
+  @R13
+  M=D
+  @ARG
+  D=M
+  @R14
+  M=D
+  @8
+  D=A
+  @R15
+  M=D
+(Main.twice_COPY2)
+  @R13
+  A=M
+  D=M
+  @R14
+  A=M
+  M=D
+  @R13
+  M=M+1
+  @R14
+  M=M+1
+  @R15
+  MD=M-1
+  @Main.twice_COPY2
+  D;JGT
+  @R14
+  D=M
+  @SP
+  M=D
+  @LCL
+  M=D
+  @Main.sum3
+  0;JMP
+// function Main.sum3 0
+(Main.sum3)
+// push argument 0
+  @ARG
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 1
+  @ARG
+  A=M+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push argument 2
+  @ARG
+  A=M+1
+  A=A+1
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// add
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @SP
+  M=M-1
+  A=M
+  D=D+M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// return
+  @LCL
+  D=M
+  @R13
+  M=D
+  @5
+  D=D-A
+  A=D
+  D=M
+  @R14
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @ARG
+  A=M
+  M=D
+  @ARG
+  D=M
+  D=D+1
+  @SP
+  M=D
+  @R13
+  D=M
+  @1
+  D=D-A
+  A=D
+  D=M
+  @THAT
+  M=D
+  @R13
+  D=M
+  @2
+  D=D-A
+  A=D
+  D=M
+  @THIS
+  M=D
+  @R13
+  D=M
+  @3
+  D=D-A
+  A=D
+  D=M
+  @ARG
+  M=D
+  @R13
+  D=M
+  @4
+  D=D-A
+  A=D
+  D=M
+  @LCL
+  M=D
+  @R14
+  A=M
+  0;JMP
+// > Sys.asm
+// function Sys.init 0
+(Sys.init)
+// push constant 600
+  @600
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// push constant 0
+  @0
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.count 2
+  @Sys.init$ret.1
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @2
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.count
+  0;JMP
+(Sys.init$ret.1)
+// pop temp 0
+  @5
+  D=A
+  @0
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// push constant 3
+  @3
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+// call Main.twice 1
+  @Sys.init$ret.2
+  D=A
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @LCL
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @ARG
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THIS
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @THAT
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
+  @SP
+  D=M
+  @5
+  D=D-A
+  @1
+  D=D-A
+  @ARG
+  M=D
+  @SP
+  D=M
+  @LCL
+  M=D
+  @Main.twice
+  0;JMP
+(Sys.init$ret.2)
+// pop temp 1
+  @5
+  D=A
+  @1
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
+// label HALT
+(Sys.init$HALT)
+// goto HALT
+  @Sys.init$HALT
+  0;JMP
@@ -1,14 +1,9 @@
 // pop argument 2
-  @ARG
-  D=M
-  @2
-  D=D+A
-  @R13
-  M=D
   @SP
   M=M-1
   A=M
   D=M
-  @R13
-  A=M
+  @ARG
+  A=M+1
+  A=A+1
   M=D
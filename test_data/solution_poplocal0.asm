@@ -0,0 +1,8 @@
+// pop local 0
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @LCL
+  A=M
+  M=D
@@ -0,0 +1,14 @@
+// pop local 7
+  @LCL
+  D=M
+  @7
+  D=D+A
+  @R13
+  M=D
+  @SP
+  M=M-1
+  A=M
+  D=M
+  @R13
+  A=M
+  M=D
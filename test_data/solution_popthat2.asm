@@ -1,14 +1,9 @@
 // pop that 2
-  @THAT
-  D=M
-  @2
-  D=D+A
-  @R13
-  M=D
   @SP
   M=M-1
   A=M
   D=M
-  @R13
-  A=M
+  @THAT
+  A=M+1
+  A=A+1
   M=D
@@ -0,0 +1,9 @@
+// push local 0
+  @LCL
+  A=M
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
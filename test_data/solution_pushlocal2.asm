@@ -1,8 +1,7 @@
 // push local 2
   @LCL
-  D=M
-  @2
-  A=D+A
+  A=M+1
+  A=A+1
   D=M
   @SP
   A=M
@@ -0,0 +1,11 @@
+// push local 3
+  @LCL
+  D=M
+  @3
+  A=D+A
+  D=M
+  @SP
+  A=M
+  M=D
+  @SP
+  M=M+1
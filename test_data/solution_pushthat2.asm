@@ -1,8 +1,7 @@
 // push that 2
   @THAT
-  D=M
-  @2
-  A=D+A
+  A=M+1
+  A=A+1
   D=M
   @SP
   A=M